@@ -332,25 +332,27 @@ pub fn pay_creator_fees<'a>(
                 assert_keys_equal(creator.address, *current_creator_info.key)?;
                 if !is_native {
                     let current_creator_token_account_info = next_account_info(remaining_accounts)?;
-                    if current_creator_token_account_info.data_is_empty() {
-                        make_ata(
-                            current_creator_token_account_info.to_account_info(),
-                            current_creator_info.to_account_info(),
-                            treasury_mint.to_account_info(),
-                            fee_payer.to_account_info(),
-                            ata_program.to_account_info(),
-                            token_program.to_account_info(),
-                            system_program.to_account_info(),
-                            rent.to_account_info(),
-                            fee_payer_seeds,
-                        )?;
-                    }
-                    assert_is_ata(
-                        current_creator_token_account_info,
-                        current_creator_info.key,
-                        &treasury_mint.key(),
-                    )?;
+                    // With larger creator lists, zero share creators are common. Their token
+                    // account is still passed to keep positions aligned, but is not created.
                     if creator_fee > 0 {
+                        if current_creator_token_account_info.data_is_empty() {
+                            make_ata(
+                                current_creator_token_account_info.to_account_info(),
+                                current_creator_info.to_account_info(),
+                                treasury_mint.to_account_info(),
+                                fee_payer.to_account_info(),
+                                ata_program.to_account_info(),
+                                token_program.to_account_info(),
+                                system_program.to_account_info(),
+                                rent.to_account_info(),
+                                fee_payer_seeds,
+                            )?;
+                        }
+                        assert_is_ata(
+                            current_creator_token_account_info,
+                            current_creator_info.key,
+                            &treasury_mint.key(),
+                        )?;
                         invoke_signed(
                             &spl_token::instruction::transfer(
                                 token_program.key,
@@ -134,6 +134,74 @@ pub enum AuctionError {
     /// Gap tick size percentage must be between 0 and 100
     #[error("Gap tick size percentage must be between 0 and 100")]
     InvalidGapTickSizePercentage,
+
+    /// Dutch auction needs capped winners, a valid price schedule and no tick sizes or instant sale price
+    #[error("Dutch auction needs capped winners, a valid price schedule and no tick sizes or instant sale price")]
+    InvalidDutchAuctionSchedule,
+
+    /// Dutch auctions need the auction extended account to start
+    #[error("Dutch auctions need the auction extended account to start")]
+    AuctionExtendedMissing,
+
+    /// Sealed bid auctions need a bidding window, capped winners and a reveal period, and can't be combined with an instant sale price or dutch auction
+    #[error("Sealed bid auctions need a bidding window, capped winners and a reveal period, and can't be combined with an instant sale price or dutch auction")]
+    InvalidSealedBidSettings,
+
+    /// This auction only takes sealed bids
+    #[error("This auction only takes sealed bids")]
+    SealedBidRequired,
+
+    /// Bidding is closed, sealed bids can only be revealed now
+    #[error("Bidding is closed, sealed bids can only be revealed now")]
+    SealedBiddingClosed,
+
+    /// Sealed bids can't be revealed until bidding is closed
+    #[error("Sealed bids can't be revealed until bidding is closed")]
+    RevealPeriodNotStarted,
+
+    /// Bid was not revealed in time and its deposit is forfeit
+    #[error("Bid was not revealed in time and its deposit is forfeit")]
+    UnrevealedBidForfeited,
+
+    /// Second price settlement is only available on capped english auctions without an instant sale price
+    #[error("Second price settlement is only available on capped english auctions without an instant sale price")]
+    InvalidSecondPriceSettings,
+
+    /// Refund account must be a token account of the bidder for the auction mint
+    #[error("Refund account must be a token account of the bidder for the auction mint")]
+    InvalidRefundAccount,
+
+    /// Bid book pages are missing or not where this bid belongs
+    #[error("Bid book pages are missing or not where this bid belongs")]
+    InvalidBidPage,
+
+    /// Bid book is only available on capped english auctions without an instant sale price or gap tick size
+    #[error("Bid book is only available on capped english auctions without an instant sale price or gap tick size")]
+    InvalidBidBookSettings,
+
+    /// Bid page has to be settled first, after every page ahead of it
+    #[error("Bid page has to be settled first, after every page ahead of it")]
+    BidPageNotSettled,
+
+    /// A reveal deadline needs a blinded price floor, an end time and a positive deadline
+    #[error("A reveal deadline needs a blinded price floor, an end time and a positive deadline")]
+    InvalidBlindedFloorSettings,
+
+    /// Auction can only be ended by anyone once bidding is over
+    #[error("Auction can only be ended by anyone once bidding is over")]
+    AuctionNotOver,
+
+    /// The authority can still reveal the price floor
+    #[error("The authority can still reveal the price floor")]
+    RevealDeadlineNotPassed,
+
+    /// The price floor can no longer be revealed
+    #[error("The price floor can no longer be revealed")]
+    RevealDeadlinePassed,
+
+    /// Bid page has already been settled
+    #[error("Bid page has already been settled")]
+    BidPageAlreadySettled,
 }
 
 impl PrintProgramError for AuctionError {
@@ -1,4 +1,4 @@
-use crate::{EXTENDED, PREFIX};
+use crate::{BID_PAGE, EXTENDED, PREFIX, SEALED_BID};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -7,9 +7,11 @@ use solana_program::{
 };
 
 pub use crate::processor::{
-    cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs, create_auction::CreateAuctionArgs,
-    create_auction_v2::CreateAuctionArgsV2, end_auction::EndAuctionArgs, place_bid::PlaceBidArgs,
-    start_auction::StartAuctionArgs,
+    cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs, crank_end_auction::CrankEndAuctionArgs,
+    create_auction::CreateAuctionArgs, create_auction_v2::CreateAuctionArgsV2,
+    create_auction_v3::CreateAuctionArgsV3, end_auction::EndAuctionArgs, place_bid::PlaceBidArgs,
+    place_sealed_bid::PlaceSealedBidArgs, reveal_bid::RevealBidArgs,
+    settle_bid_page::SettleBidPageArgs, start_auction::StartAuctionArgs,
 };
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
@@ -26,6 +28,10 @@ pub enum AuctionInstruction {
     ///   8. `[]` Rent sysvar
     ///   9. `[]` System program
     ///   10. `[]` SPL Token Program
+    ///   12. `[writable]` Bid page holding the bid (pda of ['auction', program id, auction key, 'bid_page', index]),
+    ///                   required on bid book auctions
+    ///   13. `[writable]` The bid page before it, required to empty a page that has one
+    ///   14. `[writable]` The bid page after it, required to empty a page that has one
     CancelBid(CancelBidArgs),
 
     /// Create a new auction account bound to a resource, initially in a pending state.
@@ -47,19 +53,27 @@ pub enum AuctionInstruction {
     ///   7. `[]` Clock sysvar
     ///   8. `[]` Token program
     ///   9. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   10. `[]` Optional bidder metadata (pda of ['auction', program id, auction key, bidder key, 'metadata']),
+    ///                   required to claim the deposit of an unrevealed sealed bid
+    ///   11. `[writable]` Optional bidders token account, required to refund the part of a second price
+    ///                   auction bid above the price it settled at
     ClaimBid(ClaimBidArgs),
 
     /// Ends an auction, regardless of end timing conditions
     ///
     ///   0. `[writable, signer]` Auction authority
     ///   1. `[writable]` Auction
-    ///   6. `[]` Clock sysvar
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` Optional auction extended (pda of ['auction', program id, resource id, 'extended']),
+    ///                   required to reveal a blinded price floor once bidding has closed on its own
     EndAuction(EndAuctionArgs),
 
     /// Start an inactive auction.
     ///   0. `[signer]` The creator/authorised account.
     ///   1. `[writable]` Initialized auction account.
     ///   2. `[]` Clock sysvar
+    ///   3. `[writable]` Optional auction extended (pda relative to auction of ['auction', program id, vault key, 'extended']),
+    ///                   required for dutch auctions to record when the price starts falling
     StartAuction(StartAuctionArgs),
 
     /// Update the authority for an auction account.
@@ -82,6 +96,11 @@ pub enum AuctionInstruction {
     ///   10. `[]` Rent sysvar
     ///   11. `[]` System program
     ///   12. `[]` SPL Token Program
+    ///   14. `[writable]` Bid page the bid goes on (pda of ['auction', program id, auction key, 'bid_page', index]),
+    ///                   required on bid book auctions, page 0 for the first bid
+    ///   15. `[]` The bid page before it, required when it has one
+    ///   16. `[writable]` The bid page after it, required when it has one
+    ///   17. `[writable]` Uninitialized bid page at the next unused index, required when the bid page is full
     PlaceBid(PlaceBidArgs),
 
     /// Create a new auction account bound to a resource, initially in a pending state.
@@ -92,6 +111,65 @@ pub enum AuctionInstruction {
     ///   3. `[]` Rent sysvar
     ///   4. `[]` System account
     CreateAuctionV2(CreateAuctionArgsV2),
+
+    /// Create a new auction account bound to a resource, initially in a pending state.
+    /// Same as CreateAuctionV2, with an optional price schedule that makes it a dutch auction
+    ///   0. `[signer]` The account creating the auction, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    CreateAuctionV3(CreateAuctionArgsV3),
+
+    /// Commit to a bid on a running sealed bid auction, the bid itself stays hidden until it is
+    /// revealed with RevealBid. The commitment is built with SealedBid::commitment.
+    ///   0. `[signer]` The bidders primary account, for PDA calculation/transit auth.
+    ///   1. `[writable]` The bidders token account they'll pay the deposit with
+    ///   2. `[writable]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, where the deposit will be held.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[writable]` Auction account, containing data about the auction and item being bid on.
+    ///   6. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   7. `[writable]` Token mint, for transfer instructions and verification.
+    ///   8. `[signer]` Transfer authority, for moving tokens into the bid pot.
+    ///   9. `[signer]` Payer
+    ///   10. `[]` Clock sysvar
+    ///   11. `[]` Rent sysvar
+    ///   12. `[]` System program
+    ///   13. `[]` SPL Token Program
+    ///   14. `[writable]` The sealed bid account (pda of ['auction', program id, auction key, bidder key, 'sealed_bid'])
+    PlaceSealedBid(PlaceSealedBidArgs),
+
+    /// Reveal a sealed bid once bidding has closed, ranking it among the other revealed bids and
+    /// refunding the part of the deposit above it.
+    ///   0. `[signer]` The bidders primary account, for PDA calculation/transit auth.
+    ///   1. `[writable]` The bidders token account that receives the rest of the deposit
+    ///   2. `[]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, where the deposit is held.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[]` The sealed bid account (pda of ['auction', program id, auction key, bidder key, 'sealed_bid'])
+    ///   6. `[writable]` Auction account, containing data about the auction and item being bid on.
+    ///   7. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   8. `[]` Token mint, for transfer instructions and verification.
+    ///   9. `[]` Clock sysvar
+    ///   10. `[]` SPL Token Program
+    RevealBid(RevealBidArgs),
+
+    /// Settle a page of a bid book once bidding is over, recording how many bids rank ahead of it
+    /// and copying its winning bids onto the auction. Pages have to be settled in order, starting
+    /// with page 0.
+    ///   0. `[writable]` Bid page (pda of ['auction', program id, auction key, 'bid_page', index])
+    ///   1. `[writable]` Auction account
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` The settled bid page before it, required on every page but page 0
+    SettleBidPage(SettleBidPageArgs),
+
+    /// Ends an auction once its end time and the gap after the last bid have passed, signed by
+    /// anyone. An auction with a blinded price floor also has to be past its reveal deadline.
+    ///   0. `[writable]` Auction
+    ///   1. `[]` Auction extended (pda of ['auction', program id, resource id, 'extended'])
+    ///   2. `[]` Clock sysvar
+    CrankEndAuction(CrankEndAuctionArgs),
 }
 
 /// Creates an CreateAuction instruction.
@@ -166,6 +244,42 @@ pub fn create_auction_instruction_v2(
     }
 }
 
+/// Creates an CreateAuctionV3 instruction.
+pub fn create_auction_instruction_v3(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateAuctionArgsV3,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: AuctionInstruction::CreateAuctionV3(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 /// Creates an SetAuthority instruction.
 pub fn set_authority_instruction(
     program_id: Pubkey,
@@ -200,12 +314,21 @@ pub fn start_auction_instruction(
     ];
     let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
     Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(authority_pubkey, true),
             AccountMeta::new(auction_pubkey, false),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(auction_extended_pubkey, false),
         ],
         data: AuctionInstruction::StartAuction(args).try_to_vec().unwrap(),
     }
@@ -346,6 +469,7 @@ pub fn cancel_bid_instruction(
 pub fn end_auction_instruction(
     program_id: Pubkey,
     authority_pubkey: Pubkey,
+    auction_extended_pubkey: Option<Pubkey>,
     args: EndAuctionArgs,
 ) -> Instruction {
     // Derive Auction Key
@@ -356,13 +480,19 @@ pub fn end_auction_instruction(
     ];
     let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(authority_pubkey, true),
+        AccountMeta::new(auction_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    if let Some(auction_extended) = auction_extended_pubkey {
+        accounts.push(AccountMeta::new_readonly(auction_extended, false));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(authority_pubkey, true),
-            AccountMeta::new(auction_pubkey, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-        ],
+        accounts,
         data: AuctionInstruction::EndAuction(args).try_to_vec().unwrap(),
     }
 }
@@ -375,6 +505,7 @@ pub fn claim_bid_instruction(
     bidder_pot_token_pubkey: Pubkey,
     token_mint_pubkey: Pubkey,
     auction_extended_pubkey: Option<Pubkey>,
+    bidder_token_pubkey: Option<Pubkey>,
     args: ClaimBidArgs,
 ) -> Instruction {
     // Derive Auction Key
@@ -403,11 +534,26 @@ pub fn claim_bid_instruction(
         AccountMeta::new_readonly(bidder_pubkey, false),
         AccountMeta::new_readonly(token_mint_pubkey, false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),        
+        AccountMeta::new_readonly(spl_token::id(), false),
     ];
 
     if let Some(auction_extended) = auction_extended_pubkey {
+        // Derive Bidder Meta
+        let seeds = &[
+            PREFIX.as_bytes(),
+            &program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidder_pubkey.as_ref(),
+            "metadata".as_bytes(),
+        ];
+        let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
         accounts.push(AccountMeta::new_readonly(auction_extended, false));
+        accounts.push(AccountMeta::new_readonly(bidder_meta_pubkey, false));
+
+        if let Some(bidder_token) = bidder_token_pubkey {
+            accounts.push(AccountMeta::new(bidder_token, false));
+        }
     }
 
     Instruction {
@@ -416,3 +562,320 @@ pub fn claim_bid_instruction(
         data: AuctionInstruction::ClaimBid(args).try_to_vec().unwrap(),
     }
 }
+
+/// Creates an PlaceSealedBid instruction.
+pub fn place_sealed_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    args: PlaceSealedBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Sealed Bid
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        SEALED_BID.as_bytes(),
+    ];
+    let (sealed_bid_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bidder_pubkey, true),
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new(token_mint_pubkey, false),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(sealed_bid_pubkey, false),
+        ],
+        data: AuctionInstruction::PlaceSealedBid(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an RevealBid instruction.
+pub fn reveal_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    args: RevealBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Sealed Bid
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        SEALED_BID.as_bytes(),
+    ];
+    let (sealed_bid_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(bidder_pubkey, true),
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new_readonly(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new_readonly(sealed_bid_pubkey, false),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(token_mint_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AuctionInstruction::RevealBid(args).try_to_vec().unwrap(),
+    }
+}
+
+fn bid_page_pubkey(program_id: &Pubkey, auction_pubkey: &Pubkey, index: u32) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        BID_PAGE.as_bytes(),
+        &index.to_le_bytes(),
+    ];
+    let (bid_page_pubkey, _) = Pubkey::find_program_address(seeds, program_id);
+    bid_page_pubkey
+}
+
+/// Creates a PlaceBid instruction for a bid book auction, placing the bid on the given page. Pages
+/// that aren't there are filled in with the bid page itself.
+pub fn place_bid_on_book_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    bid_page: u32,
+    prev_bid_page: Option<u32>,
+    next_bid_page: Option<u32>,
+    new_bid_page: u32,
+    args: PlaceBidArgs,
+) -> Instruction {
+    let mut instruction = place_bid_instruction(
+        program_id,
+        bidder_pubkey,
+        bidder_token_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        transfer_authority,
+        payer,
+        args,
+    );
+    let auction_pubkey = instruction.accounts[5].pubkey;
+
+    let page_pubkey = bid_page_pubkey(&program_id, &auction_pubkey, bid_page);
+    let page = |index: Option<u32>| match index {
+        Some(index) => bid_page_pubkey(&program_id, &auction_pubkey, index),
+        None => page_pubkey,
+    };
+
+    instruction.accounts.extend(vec![
+        AccountMeta::new(page_pubkey, false),
+        AccountMeta::new_readonly(page(prev_bid_page), false),
+        AccountMeta::new(page(next_bid_page), false),
+        AccountMeta::new(page(Some(new_bid_page)), false),
+    ]);
+    instruction
+}
+
+/// Creates a CancelBid instruction for a bid book auction, taking the bid off the given page.
+pub fn cancel_bid_on_book_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    bid_page: u32,
+    prev_bid_page: Option<u32>,
+    next_bid_page: Option<u32>,
+    args: CancelBidArgs,
+) -> Instruction {
+    let mut instruction = cancel_bid_instruction(
+        program_id,
+        bidder_pubkey,
+        bidder_token_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        args,
+    );
+    let auction_pubkey = instruction.accounts[5].pubkey;
+
+    let page_pubkey = bid_page_pubkey(&program_id, &auction_pubkey, bid_page);
+    let page = |index: Option<u32>| match index {
+        Some(index) => bid_page_pubkey(&program_id, &auction_pubkey, index),
+        None => page_pubkey,
+    };
+
+    instruction.accounts.extend(vec![
+        AccountMeta::new(page_pubkey, false),
+        AccountMeta::new(page(prev_bid_page), false),
+        AccountMeta::new(page(next_bid_page), false),
+    ]);
+    instruction
+}
+
+/// Creates a SettleBidPage instruction.
+pub fn settle_bid_page_instruction(
+    program_id: Pubkey,
+    bid_page: u32,
+    prev_bid_page: Option<u32>,
+    args: SettleBidPageArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(
+            bid_page_pubkey(&program_id, &auction_pubkey, bid_page),
+            false,
+        ),
+        AccountMeta::new(auction_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    if let Some(prev_bid_page) = prev_bid_page {
+        accounts.push(AccountMeta::new_readonly(
+            bid_page_pubkey(&program_id, &auction_pubkey, prev_bid_page),
+            false,
+        ));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: AuctionInstruction::SettleBidPage(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates a CrankEndAuction instruction.
+pub fn crank_end_auction_instruction(program_id: Pubkey, args: CrankEndAuctionArgs) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Auction Extended Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::CrankEndAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
@@ -11,4 +11,10 @@ pub mod processor;
 pub const PREFIX: &str = "auction";
 
 pub const EXTENDED: &str = "extended";
+
+/// Suffix of the PDA holding the hash of a sealed bid until it is revealed.
+pub const SEALED_BID: &str = "sealed_bid";
+
+/// Suffix of the PDAs holding the pages of a bid book, followed by the page index.
+pub const BID_PAGE: &str = "bid_page";
 solana_program::declare_id!("auctxRXPeJoc4817jDhf4HbjnhEcr1cCXenosMhK5R8");
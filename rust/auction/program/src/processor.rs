@@ -2,29 +2,45 @@ use crate::errors::AuctionError;
 use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::UnixTimestamp,
-    entrypoint::ProgramResult, hash::Hash, msg, program_error::ProgramError, pubkey::Pubkey,
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    clock::UnixTimestamp,
+    entrypoint::ProgramResult,
+    hash::{self, Hash},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
 };
 use std::{cell::Ref, cmp, mem};
 
 // Declare submodules, each contains a single handler for each instruction variant in the program.
 pub mod cancel_bid;
 pub mod claim_bid;
+pub mod crank_end_auction;
 pub mod create_auction;
 pub mod create_auction_v2;
+pub mod create_auction_v3;
 pub mod end_auction;
 pub mod place_bid;
+pub mod place_sealed_bid;
+pub mod reveal_bid;
 pub mod set_authority;
+pub mod settle_bid_page;
 pub mod start_auction;
 
 // Re-export submodules handlers + associated types for other programs to consume.
 pub use cancel_bid::*;
 pub use claim_bid::*;
+pub use crank_end_auction::*;
 pub use create_auction::*;
 pub use create_auction_v2::*;
+pub use create_auction_v3::*;
 pub use end_auction::*;
 pub use place_bid::*;
+pub use place_sealed_bid::*;
+pub use reveal_bid::*;
 pub use set_authority::*;
+pub use settle_bid_page::*;
 pub use start_auction::*;
 
 pub fn process_instruction(
@@ -36,14 +52,24 @@ pub fn process_instruction(
     match AuctionInstruction::try_from_slice(input)? {
         AuctionInstruction::CancelBid(args) => cancel_bid(program_id, accounts, args),
         AuctionInstruction::ClaimBid(args) => claim_bid(program_id, accounts, args),
-        AuctionInstruction::CreateAuction(args) => {
-            create_auction(program_id, accounts, args, None, None)
-        }
+        AuctionInstruction::CreateAuction(args) => create_auction(
+            program_id,
+            accounts,
+            args,
+            None,
+            None,
+            AuctionSettings::default(),
+        ),
         AuctionInstruction::CreateAuctionV2(args) => create_auction_v2(program_id, accounts, args),
+        AuctionInstruction::CreateAuctionV3(args) => create_auction_v3(program_id, accounts, args),
+        AuctionInstruction::PlaceSealedBid(args) => place_sealed_bid(program_id, accounts, args),
+        AuctionInstruction::RevealBid(args) => reveal_bid(program_id, accounts, args),
         AuctionInstruction::EndAuction(args) => end_auction(program_id, accounts, args),
         AuctionInstruction::PlaceBid(args) => place_bid(program_id, accounts, args),
         AuctionInstruction::SetAuthority => set_authority(program_id, accounts),
         AuctionInstruction::StartAuction(args) => start_auction(program_id, accounts, args),
+        AuctionInstruction::SettleBidPage(args) => settle_bid_page(program_id, accounts, args),
+        AuctionInstruction::CrankEndAuction(args) => crank_end_auction(program_id, accounts, args),
     }
 }
 
@@ -61,6 +87,142 @@ pub enum PriceFloor {
     BlindedPrice(Hash),
 }
 
+/// How the price of a dutch auction falls from its start price to its floor.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum PriceDecay {
+    /// Price falls a little every second.
+    Linear,
+    /// Price falls in equal steps, one every given number of seconds.
+    Stepped(UnixTimestamp),
+}
+
+/// Price schedule of a dutch auction, the price starts high and falls until someone bids.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct DutchAuctionSchedule {
+    /// Price when the auction starts.
+    pub start_price: u64,
+    /// Lowest price, reached at the end of the decay and held until the auction ends.
+    pub floor_price: u64,
+    /// Seconds after the start it takes the price to fall to the floor.
+    pub decay_duration: UnixTimestamp,
+    /// Whether the price falls continuously or in steps.
+    pub decay: PriceDecay,
+}
+
+impl DutchAuctionSchedule {
+    pub fn assert_valid(&self) -> ProgramResult {
+        if self.start_price < self.floor_price || self.decay_duration <= 0 {
+            return Err(AuctionError::InvalidDutchAuctionSchedule.into());
+        }
+
+        if let PriceDecay::Stepped(interval) = self.decay {
+            if interval <= 0 || interval > self.decay_duration {
+                return Err(AuctionError::InvalidDutchAuctionSchedule.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Price a bid has to meet at `now` for an auction started at `started_at`.
+    pub fn price_at(&self, started_at: UnixTimestamp, now: UnixTimestamp) -> u64 {
+        let elapsed = now.saturating_sub(started_at).max(0);
+        if elapsed >= self.decay_duration {
+            return self.floor_price;
+        }
+
+        // Stepped prices only drop at the end of each interval.
+        let elapsed = match self.decay {
+            PriceDecay::Linear => elapsed,
+            PriceDecay::Stepped(interval) => elapsed - elapsed % interval,
+        };
+
+        // Use u128 so the multiplication can't overflow before dividing, the drop can never be
+        // larger than the difference between the two prices.
+        let drop = (self.start_price - self.floor_price) as u128 * elapsed as u128
+            / self.decay_duration as u128;
+
+        self.start_price - drop as u64
+    }
+}
+
+/// What happens to sealed bids that weren't revealed before the auction ended.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum UnrevealedBids {
+    /// The bidder can cancel the bid to get their deposit back.
+    Refund,
+    /// The deposit is kept and can be claimed by the auction authority.
+    Forfeit,
+}
+
+/// Settings of a sealed bid auction, where bids are committed as hashes and only revealed once
+/// bidding is over.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SealedBidSettings {
+    /// Seconds after bidding closes in which bids can be revealed, the auction ends after it.
+    pub reveal_period: UnixTimestamp,
+    /// What happens to deposits of bids that weren't revealed.
+    pub unrevealed_bids: UnrevealedBids,
+}
+
+impl SealedBidSettings {
+    /// Whether bidding has closed and the reveal period of an auction ending at `ended_at` has
+    /// started.
+    pub fn is_revealing(
+        &self,
+        ended_at: Option<UnixTimestamp>,
+        now: UnixTimestamp,
+    ) -> Result<bool, ProgramError> {
+        let end = ended_at.ok_or(AuctionError::InvalidState)?;
+        let reveal_start = end
+            .checked_sub(self.reveal_period)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        Ok(now > reveal_start)
+    }
+}
+
+/// What happens to an auction whose blinded price floor wasn't revealed by the deadline.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum UnrevealedFloor {
+    /// The auction goes ahead as if it never had a price floor.
+    NoFloor,
+    /// Nobody wins, every bidder can cancel their bid to get it back.
+    RefundBids,
+}
+
+/// Settings of an auction with a blinded price floor, so that it can be ended without the
+/// authority revealing the floor.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct BlindedFloorSettings {
+    /// Seconds after bidding closes in which the authority can reveal the floor with EndAuction.
+    pub reveal_deadline: UnixTimestamp,
+    /// What happens to the auction if the floor wasn't revealed by then.
+    pub unrevealed_floor: UnrevealedFloor,
+}
+
+impl BlindedFloorSettings {
+    /// Whether the floor of an auction can no longer be revealed.
+    pub fn is_past_deadline(
+        &self,
+        auction: &AuctionData,
+        now: UnixTimestamp,
+    ) -> Result<bool, ProgramError> {
+        let closed_at = auction
+            .bidding_closed_at()?
+            .ok_or(AuctionError::InvalidState)?;
+        let deadline = closed_at
+            .checked_add(self.reveal_deadline)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        Ok(now > deadline)
+    }
+}
+
 // The two extra 8's are present, one 8 is for the Vec's amount of elements and one is for the max
 // usize in bid state.
 // NOTE: New research suggests u32s are used for vecs in borsh, not u64s, so the first extra 8 should be a 4
@@ -98,7 +260,7 @@ pub struct AuctionData {
 // Alias for auction name.
 pub type AuctionName = [u8; 32];
 
-pub const MAX_AUCTION_DATA_EXTENDED_SIZE: usize = 8 + 9 + 2 + 9 + 33 + 158;
+pub const MAX_AUCTION_DATA_EXTENDED_SIZE: usize = 8 + 9 + 2 + 9 + 33 + 34 + 9 + 10 + 10 + 95;
 // Further storage for more fields. Would like to store more on the main data but due
 // to a borsh issue that causes more added fields to inflict "Access violation" errors
 // during redemption in main Metaplex app for no reason, we had to add this nasty PDA.
@@ -116,6 +278,14 @@ pub struct AuctionDataExtended {
     pub instant_sale_price: Option<u64>,
     /// Auction name
     pub name: Option<AuctionName>,
+    /// Price schedule, only set on dutch auctions
+    pub dutch_auction: Option<DutchAuctionSchedule>,
+    /// Time the auction was started, recorded when StartAuction is given this account
+    pub started_at: Option<UnixTimestamp>,
+    /// Reveal settings, only set on sealed bid auctions
+    pub sealed_bid: Option<SealedBidSettings>,
+    /// Reveal deadline of a blinded price floor
+    pub blinded_floor: Option<BlindedFloorSettings>,
 }
 
 impl AuctionDataExtended {
@@ -129,6 +299,15 @@ impl AuctionDataExtended {
         Ok(auction_extended)
     }
 
+    /// Current price of a dutch auction, None for any other kind of auction or one that hasn't
+    /// started.
+    pub fn dutch_auction_price(&self, now: UnixTimestamp) -> Option<u64> {
+        match (&self.dutch_auction, self.started_at) {
+            (Some(schedule), Some(started_at)) => Some(schedule.price_at(started_at, now)),
+            _ => None,
+        }
+    }
+
     pub fn get_instant_sale_price<'a>(data: &'a Ref<'a, &'a mut [u8]>) -> Option<u64> {
         if let Some(idx) = Self::find_instant_sale_beginning(data) {
             Some(u64::from_le_bytes(*array_ref![data, idx, 8]))
@@ -182,11 +361,45 @@ impl AuctionData {
         }
     }
 
+    pub fn is_dutch_auction(a: &AccountInfo) -> bool {
+        // Same as above, but only step back over the u32 to land on the bid state enum.
+        let bid_state_type = AuctionData::find_bid_state_beginning(a) - 1 - 4;
+        a.data.borrow()[bid_state_type] == 2
+    }
+
+    pub fn is_bid_book(a: &AccountInfo) -> bool {
+        let bid_state_type = AuctionData::find_bid_state_beginning(a) - 1 - 4;
+        a.data.borrow()[bid_state_type] == 5
+    }
+
     pub fn get_num_winners(a: &AccountInfo) -> usize {
+        if AuctionData::is_bid_book(a) {
+            let (max, len) = AuctionData::get_bid_book_info(a);
+            return std::cmp::min(len, max);
+        }
         let (bid_state_beginning, num_elements, max) = AuctionData::get_vec_info(a);
         std::cmp::min(num_elements, max)
     }
 
+    fn get_bid_book_info(a: &AccountInfo) -> (usize, usize) {
+        // The winner limit follows the winners like on any other auction, then the number of bids.
+        let (bid_state_beginning, num_elements, max) = AuctionData::get_vec_info(a);
+        let data = a.data.borrow();
+        let len_data = array_ref![data, bid_state_beginning + BID_LENGTH * num_elements + 8, 8];
+        let len = u64::from_le_bytes(*len_data) as usize;
+
+        (max, len)
+    }
+
+    /// Whether get_is_winner and get_winner_at know every winner, see BidState::winners_settled.
+    pub fn get_winners_settled(a: &AccountInfo) -> bool {
+        if !AuctionData::is_bid_book(a) {
+            return true;
+        }
+        let (_, num_elements, _) = AuctionData::get_vec_info(a);
+        num_elements >= AuctionData::get_num_winners(a)
+    }
+
     fn find_bid_state_beginning(a: &AccountInfo) -> usize {
         let data = a.data.borrow();
         let mut bid_state_beginning = 32 + 32;
@@ -350,6 +563,20 @@ impl AuctionData {
         };
     }
 
+    /// When the last bid could have been placed, which is the end time pushed back by the gap
+    /// after the last bid. None while the auction has no end time.
+    pub fn bidding_closed_at(&self) -> Result<Option<UnixTimestamp>, ProgramError> {
+        match (self.ended_at, self.end_auction_gap, self.last_bid) {
+            (Some(end), Some(gap), Some(last)) => {
+                let next_bid_time = last
+                    .checked_add(gap)
+                    .ok_or(AuctionError::NumericalOverflowError)?;
+                Ok(Some(cmp::max(end, next_bid_time)))
+            }
+            (end, _, _) => Ok(end),
+        }
+    }
+
     pub fn is_winner(&self, key: &Pubkey) -> Option<usize> {
         let minimum = match self.price_floor {
             PriceFloor::MinimumPrice(min) => min[0],
@@ -358,6 +585,24 @@ impl AuctionData {
         self.bid_state.is_winner(key, minimum)
     }
 
+    /// What the winner at idx pays, see BidState::payment_amount.
+    pub fn payment_amount(&self, idx: usize) -> u64 {
+        let minimum = match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        };
+        self.bid_state.payment_amount(idx, minimum)
+    }
+
+    /// Same as is_winner for a bid book, given the page holding the bid.
+    pub fn is_winner_on_page(&self, bid_page: &BidPage, key: &Pubkey) -> Option<usize> {
+        let minimum = match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        };
+        bid_page.is_winner(key, self.num_possible_winners() as usize, minimum)
+    }
+
     pub fn num_winners(&self) -> u64 {
         self.bid_state.num_winners()
     }
@@ -467,6 +712,10 @@ pub struct Bid(pub Pubkey, pub u64);
 pub enum BidState {
     EnglishAuction { bids: Vec<Bid>, max: usize },
     OpenEdition { bids: Vec<Bid>, max: usize },
+    DutchAuction { bids: Vec<Bid>, max: usize },
+    SealedBidAuction { bids: Vec<Bid>, max: usize },
+    SecondPriceAuction { bids: Vec<Bid>, max: usize },
+    BidBook { winners: Vec<Bid>, max: usize, len: usize, pages: u32 },
 }
 
 /// Bidding Implementations.
@@ -476,6 +725,20 @@ pub enum BidState {
 ///
 /// Open Edition: All bids are accepted, cancellations return money to the bidder and always
 /// succeed.
+///
+/// Dutch Auction: bids are only accepted at the current price and each one wins straight away,
+/// so this stores every bid in the order they came in until all the lots are taken.
+///
+/// Sealed Bid Auction: bids are hidden until bidding is over, and ranked like an English auction
+/// as they are revealed.
+///
+/// Second Price Auction: bids are ranked like an English auction, but every winner pays the
+/// highest losing bid, or the price floor if that is higher, rather than their own bid.
+///
+/// Bid Book: an English auction that keeps every bid on BidPage accounts rather than inline, so
+/// the auction account only has to fit the winners rather than every bid. Besides the number of
+/// bids and pages, this stores the winning bids in the same order as an English auction, copied
+/// off the pages as SettleBidPage ranks them.
 impl BidState {
     pub fn new_english(n: usize) -> Self {
         BidState::EnglishAuction {
@@ -484,6 +747,36 @@ impl BidState {
         }
     }
 
+    pub fn new_dutch(n: usize) -> Self {
+        BidState::DutchAuction {
+            bids: vec![],
+            max: n,
+        }
+    }
+
+    pub fn new_sealed_bid(n: usize) -> Self {
+        BidState::SealedBidAuction {
+            bids: vec![],
+            max: n,
+        }
+    }
+
+    pub fn new_second_price(n: usize) -> Self {
+        BidState::SecondPriceAuction {
+            bids: vec![],
+            max: n,
+        }
+    }
+
+    pub fn new_bid_book(n: usize) -> Self {
+        BidState::BidBook {
+            winners: vec![],
+            max: n,
+            len: 0,
+            pages: 0,
+        }
+    }
+
     pub fn new_open_edition() -> Self {
         BidState::OpenEdition {
             bids: vec![],
@@ -501,7 +794,7 @@ impl BidState {
         real_max
     }
 
-    fn assert_valid_tick_size_bid(bid: &Bid, tick_size: Option<u64>) -> ProgramResult {
+    pub fn assert_valid_tick_size_bid(bid: &Bid, tick_size: Option<u64>) -> ProgramResult {
         if let Some(tick) = tick_size {
             if bid.1.checked_rem(tick) != Some(0) {
                 msg!(
@@ -559,7 +852,9 @@ impl BidState {
 
         match self {
             // In a capped auction, track the limited number of winners.
-            BidState::EnglishAuction { ref mut bids, max } => {
+            BidState::EnglishAuction { ref mut bids, max }
+            | BidState::SealedBidAuction { ref mut bids, max }
+            | BidState::SecondPriceAuction { ref mut bids, max } => {
                 match bids.last() {
                     Some(top) => {
                         msg!("Looking to go over the loop, but check tick size first");
@@ -625,6 +920,18 @@ impl BidState {
 
             // In an open auction, bidding simply succeeds.
             BidState::OpenEdition { bids, max } => Ok(()),
+
+            // In a dutch auction every bid wins, the first one in is winner #1.
+            BidState::DutchAuction { ref mut bids, max } => {
+                if bids.len() >= *max {
+                    return Err(AuctionError::InvalidState.into());
+                }
+                bids.insert(0, bid);
+                Ok(())
+            }
+
+            // Bids on a bid book go onto its pages, see BidPage::place_bid.
+            BidState::BidBook { .. } => Err(AuctionError::InvalidState.into()),
         }
     }
 
@@ -632,7 +939,10 @@ impl BidState {
     /// function simple no-ops.
     pub fn cancel_bid(&mut self, key: Pubkey) -> Result<(), ProgramError> {
         match self {
-            BidState::EnglishAuction { ref mut bids, max } => {
+            BidState::EnglishAuction { ref mut bids, max }
+            | BidState::DutchAuction { ref mut bids, max }
+            | BidState::SealedBidAuction { ref mut bids, max }
+            | BidState::SecondPriceAuction { ref mut bids, max } => {
                 bids.retain(|b| b.0 != key);
                 Ok(())
             }
@@ -640,19 +950,51 @@ impl BidState {
             // In an open auction, cancelling simply succeeds. It's up to the manager of an auction
             // to decide what to do with open edition bids.
             BidState::OpenEdition { bids, max } => Ok(()),
+
+            // Bids on a bid book are taken off its pages, so only the count changes here.
+            BidState::BidBook { ref mut len, .. } => {
+                *len = len
+                    .checked_sub(1)
+                    .ok_or(AuctionError::NumericalOverflowError)?;
+                Ok(())
+            }
         }
     }
 
     pub fn amount(&self, index: usize) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max }
+            | BidState::SealedBidAuction { bids, max }
+            | BidState::SecondPriceAuction { bids, max }
+            | BidState::BidBook {
+                winners: bids, max, ..
+            } => {
                 if index >= 0 as usize && index < bids.len() {
                     return bids[bids.len() - index - 1].1;
                 } else {
                     return 0;
                 }
             }
-            BidState::OpenEdition { bids, max } => 0,
+            BidState::OpenEdition { .. } => 0,
+        }
+    }
+
+    /// What the winner at index pays for their lot. This is the bid itself, except on a second
+    /// price auction where every winner pays the highest losing bid or min, whichever is higher.
+    pub fn payment_amount(&self, index: usize, min: u64) -> u64 {
+        match self {
+            BidState::SecondPriceAuction { bids, max } => {
+                // The array keeps more bids than there are winners, so the first loser is still
+                // around unless nobody was outbid.
+                let highest_losing_bid = if bids.len() > *max {
+                    bids[bids.len() - *max - 1].1
+                } else {
+                    0
+                };
+                cmp::min(cmp::max(highest_losing_bid, min), self.amount(index))
+            }
+            _ => self.amount(index),
         }
     }
 
@@ -662,7 +1004,13 @@ impl BidState {
 
         match self {
             // Presense in the winner list is enough to check win state.
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max }
+            | BidState::SealedBidAuction { bids, max }
+            | BidState::SecondPriceAuction { bids, max }
+            | BidState::BidBook {
+                winners: bids, max, ..
+            } => {
                 match bids.iter().position(|bid| &bid.0 == key && bid.1 >= min) {
                     Some(val) => {
                         let zero_based_index = bids.len() - val - 1;
@@ -683,22 +1031,49 @@ impl BidState {
 
     pub fn num_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => cmp::min(bids.len(), *max) as u64,
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max }
+            | BidState::SealedBidAuction { bids, max }
+            | BidState::SecondPriceAuction { bids, max } => cmp::min(bids.len(), *max) as u64,
+            BidState::BidBook { max, len, .. } => cmp::min(*len, *max) as u64,
             BidState::OpenEdition { bids, max } => 0,
         }
     }
 
     pub fn num_possible_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => *max as u64,
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max }
+            | BidState::SealedBidAuction { bids, max }
+            | BidState::SecondPriceAuction { bids, max } => *max as u64,
+            BidState::BidBook { max, .. } => *max as u64,
             BidState::OpenEdition { bids, max } => 0,
         }
     }
 
+    /// Leave no lots to win, so that every bid can be cancelled to get it back.
+    pub fn clear_winners(&mut self) {
+        match self {
+            BidState::EnglishAuction { max, .. }
+            | BidState::DutchAuction { max, .. }
+            | BidState::SealedBidAuction { max, .. }
+            | BidState::SecondPriceAuction { max, .. }
+            | BidState::BidBook { max, .. } => *max = 0,
+            // Open editions have no winners to begin with.
+            BidState::OpenEdition { .. } => (),
+        }
+    }
+
     /// Idea is to present #1 winner as index 0 to outside world with this method
     pub fn winner_at(&self, index: usize) -> Option<Pubkey> {
         match self {
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max }
+            | BidState::DutchAuction { bids, max }
+            | BidState::SealedBidAuction { bids, max }
+            | BidState::SecondPriceAuction { bids, max }
+            | BidState::BidBook {
+                winners: bids, max, ..
+            } => {
                 if index < *max && index < bids.len() {
                     let bid = &bids[bids.len() - index - 1];
                     Some(bids[bids.len() - index - 1].0)
@@ -706,7 +1081,28 @@ impl BidState {
                     None
                 }
             }
-            BidState::OpenEdition { bids, max } => None,
+            BidState::OpenEdition { .. } => None,
+        }
+    }
+
+    /// Copy the winning bids off a page that was just settled. Pages are settled from the highest
+    /// bids down, so these rank below every winner copied so far.
+    pub fn add_winners_from_page(&mut self, bid_page: &BidPage) -> ProgramResult {
+        if let BidState::BidBook { winners, max, .. } = self {
+            let rank_offset = bid_page
+                .rank_offset
+                .ok_or(AuctionError::BidPageNotSettled)? as usize;
+            let page_winners = max.saturating_sub(rank_offset).min(bid_page.bids.len());
+            winners.splice(0..0, bid_page.bids[..page_winners].iter().rev().cloned());
+        }
+        Ok(())
+    }
+
+    /// Whether every winner is known, which on a bid book takes settling the pages holding them.
+    pub fn winners_settled(&self) -> bool {
+        match self {
+            BidState::BidBook { winners, .. } => winners.len() as u64 >= self.num_winners(),
+            _ => true,
         }
     }
 
@@ -717,6 +1113,9 @@ impl BidState {
                 // bids.len() - max = index of the last winner bid
                 bids.len() >= *max && bids[bids.len() - *max].1 >= instant_sale_amount
             }
+            // Every dutch auction bid is made at the current price, so it's over once all lots
+            // are taken.
+            BidState::DutchAuction { bids, max } => bids.len() >= *max,
             _ => false,
         }
     }
@@ -729,6 +1128,160 @@ pub enum WinnerLimit {
     Capped(usize),
 }
 
+pub const SEALED_BID_LEN: usize = 32 + 32 + 32;
+/// Hash of a sealed bid, kept in a PDA next to the bidder's metadata until the bid is revealed.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SealedBid {
+    // Relationship with the bidder who placed this bid.
+    pub bidder_pubkey: Pubkey,
+    // Relationship with the auction this bid was placed on.
+    pub auction_pubkey: Pubkey,
+    // Hash of the auction, bidder, amount and salt, see SealedBid::commitment.
+    pub commitment: Hash,
+}
+
+impl SealedBid {
+    pub fn from_account_info(a: &AccountInfo) -> Result<SealedBid, ProgramError> {
+        if a.data_len() != SEALED_BID_LEN {
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+
+        let sealed_bid: SealedBid = try_from_slice_unchecked(&a.data.borrow_mut())?;
+
+        Ok(sealed_bid)
+    }
+
+    /// Hash of a bid that RevealBid checks against. The auction and bidder keys are part of it,
+    /// so a commitment copied from another bidder or auction can't be revealed without knowing
+    /// the amount behind it.
+    pub fn commitment(auction: &Pubkey, bidder: &Pubkey, amount: u64, salt: u64) -> Hash {
+        hash::hashv(&[
+            auction.as_ref(),
+            bidder.as_ref(),
+            &amount.to_be_bytes(),
+            &salt.to_be_bytes(),
+        ])
+    }
+}
+
+pub const BID_PAGE_CAPACITY: usize = 64;
+pub const BID_PAGE_LEN: usize = 32 + 4 + 5 + 5 + 9 + 4 + BID_LENGTH * BID_PAGE_CAPACITY;
+/// A page of a bid book, stored in a PDA of ['auction', program id, auction key, 'bid_page', index].
+/// Pages form a list starting at page 0 that runs from the highest bids to the lowest, and every
+/// page keeps its own bids highest first, earliest first between equal bids.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct BidPage {
+    // Relationship with the auction this page belongs to.
+    pub auction: Pubkey,
+    // Index the page was derived with.
+    pub index: u32,
+    // Page holding the next higher bids, None on page 0 and on pages taken out of the list.
+    pub prev: Option<u32>,
+    // Page holding the next lower bids.
+    pub next: Option<u32>,
+    // Number of bids on the pages before this one, recorded by SettleBidPage once bidding is over.
+    pub rank_offset: Option<u64>,
+    // Bids on this page.
+    pub bids: Vec<Bid>,
+}
+
+impl BidPage {
+    pub fn from_account_info(a: &AccountInfo) -> Result<BidPage, ProgramError> {
+        if a.data_len() != BID_PAGE_LEN {
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+
+        let bid_page: BidPage = try_from_slice_unchecked(&a.data.borrow_mut())?;
+
+        Ok(bid_page)
+    }
+
+    /// Whether the page is still part of the list, emptied pages other than page 0 are dropped.
+    pub fn is_linked(&self) -> bool {
+        self.index == 0 || self.prev.is_some()
+    }
+
+    /// Check that a bid belongs on this page, given the pages either side of it. Equal bids rank
+    /// by time, so a bid has to go after every bid that isn't lower than it.
+    pub fn assert_valid_placement(
+        &self,
+        amount: u64,
+        prev: Option<&BidPage>,
+        next: Option<&BidPage>,
+    ) -> ProgramResult {
+        if !self.is_linked() {
+            return Err(AuctionError::InvalidBidPage.into());
+        }
+
+        if let Some(prev) = prev {
+            match prev.bids.last() {
+                // Only page 0 stays in the list once it is empty, and there is nothing above it.
+                None => (),
+                Some(lowest) if lowest.1 >= amount => (),
+                _ => return Err(AuctionError::InvalidBidPage.into()),
+            }
+        }
+
+        if let Some(next) = next {
+            match next.bids.first() {
+                Some(highest) if highest.1 < amount => (),
+                _ => return Err(AuctionError::InvalidBidPage.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Insert a bid after every bid on the page that isn't lower than it.
+    pub fn place_bid(&mut self, bid: Bid) {
+        let position = self
+            .bids
+            .iter()
+            .position(|placed| placed.1 < bid.1)
+            .unwrap_or_else(|| self.bids.len());
+        self.bids.insert(position, bid);
+    }
+
+    /// Take a bid off the page, returning whether there was one for this key.
+    pub fn cancel_bid(&mut self, key: &Pubkey) -> bool {
+        let len = self.bids.len();
+        self.bids.retain(|bid| bid.0 != *key);
+        self.bids.len() != len
+    }
+
+    /// Move the lower half of an overfull page onto a new page that follows it.
+    pub fn split(&mut self, index: u32) -> BidPage {
+        let lower = self.bids.split_off((self.bids.len() + 1) / 2);
+        let page = BidPage {
+            auction: self.auction,
+            index,
+            prev: Some(self.index),
+            next: self.next,
+            rank_offset: None,
+            bids: lower,
+        };
+        self.next = Some(index);
+        page
+    }
+
+    /// Same as BidState::is_winner, only known once the page is settled.
+    pub fn is_winner(&self, key: &Pubkey, max: usize, min: u64) -> Option<usize> {
+        let rank_offset = self.rank_offset? as usize;
+        let position = self
+            .bids
+            .iter()
+            .position(|bid| &bid.0 == key && bid.1 >= min)?;
+        let index = rank_offset + position;
+        if index < max {
+            Some(index)
+        } else {
+            None
+        }
+    }
+}
+
 pub const BIDDER_METADATA_LEN: usize = 32 + 32 + 8 + 8 + 1;
 /// Models a set of metadata for a bidder, meant to be stored in a PDA. This allows looking up
 /// information about a bidder regardless of if they have won, lost or cancelled.
@@ -3,14 +3,23 @@
 //! 1) The auction is still going on, in which case it is possible to cancel a bid at any time.
 //! 2) The auction has finished, but the bid did not win. This allows users to claim back their
 //!    funds from bid accounts.
+//!
+//! Sealed bids are binding once bidding closes, they can only be cancelled if they lost or if they
+//! were never revealed and the auction refunds unrevealed bids.
+//!
+//! On a bid book the page holding the bid has to be given, and once bidding is over it has to be
+//! settled so that it's known whether the bid won.
 
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    processor::{
+        AuctionData, AuctionDataExtended, BidPage, BidState, BidderMetadata, BidderPot,
+        UnrevealedBids,
+    },
     utils::{
-        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
-        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
-        TokenTransferParams,
+        assert_bid_page, assert_derivation, assert_initialized, assert_linked_bid_page,
+        assert_owned_by, assert_signer, assert_token_program_matches_package,
+        create_or_allocate_account_raw, spl_token_transfer, TokenTransferParams,
     },
     EXTENDED, PREFIX,
 };
@@ -52,6 +61,9 @@ struct Accounts<'a, 'b: 'a> {
     rent: &'a AccountInfo<'b>,
     system: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
+    bid_page: Option<&'a AccountInfo<'b>>,
+    prev_bid_page: Option<&'a AccountInfo<'b>>,
+    next_bid_page: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -72,6 +84,9 @@ fn parse_accounts<'a, 'b: 'a>(
         rent: next_account_info(account_iter)?,
         system: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
+        bid_page: next_account_info(account_iter).ok(),
+        prev_bid_page: next_account_info(account_iter).ok(),
+        next_bid_page: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
@@ -178,14 +193,44 @@ pub fn cancel_bid(
         return Err(AuctionError::BidderPotDoesNotExist.into());
     }
 
+    // A bid on a bid book can only be found on the page holding it.
+    let bid_page = match auction.bid_state {
+        BidState::BidBook { .. } => {
+            let bid_page_info = accounts.bid_page.ok_or(AuctionError::InvalidBidPage)?;
+            let bid_page = assert_bid_page(program_id, accounts.auction, bid_page_info)?;
+            let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+            if !metadata.cancelled
+                && !bid_page
+                    .bids
+                    .iter()
+                    .any(|bid| bid.0 == *accounts.bidder.key)
+            {
+                return Err(AuctionError::InvalidBidPage.into());
+            }
+            if auction.ended(clock.unix_timestamp)? && bid_page.rank_offset.is_none() {
+                return Err(AuctionError::BidPageNotSettled.into());
+            }
+            Some(bid_page)
+        }
+        _ => None,
+    };
+
     // Refuse to cancel if the auction ended and this person is a winning account.
-    let winner_bid_index = auction.is_winner(accounts.bidder.key);
+    let winner_bid_index = match bid_page {
+        Some(ref bid_page) => auction.is_winner_on_page(bid_page, accounts.bidder.key),
+        None => auction.is_winner(accounts.bidder.key),
+    };
     if auction.ended(clock.unix_timestamp)? && winner_bid_index.is_some() {
         return Err(AuctionError::InvalidState.into());
     }
 
     // Refuse to cancel if bidder set price above or equal instant_sale_price
     if let Some(bid_index) = winner_bid_index {
+        // Dutch auction winners bought at the current price, same as an instant sale.
+        if let BidState::DutchAuction { .. } = auction.bid_state {
+            return Err(AuctionError::InvalidState.into());
+        }
+
         if let Some(instant_sale_price) = auction_extended.instant_sale_price {
             if auction.bid_state.amount(bid_index) >= instant_sale_price {
                 return Err(AuctionError::InvalidState.into());
@@ -193,6 +238,36 @@ pub fn cancel_bid(
         }
     }
 
+    if let BidState::SealedBidAuction { .. } = auction.bid_state {
+        assert_derivation(
+            program_id,
+            accounts.auction_extended,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                EXTENDED.as_bytes(),
+            ],
+        )?;
+        let sealed_bid = auction_extended
+            .sealed_bid
+            .as_ref()
+            .ok_or(AuctionError::InvalidState)?;
+
+        if sealed_bid.is_revealing(auction.ended_at, clock.unix_timestamp)? {
+            // Revealed bids can't be taken back while they are winning.
+            if winner_bid_index.is_some() {
+                return Err(AuctionError::InvalidState.into());
+            }
+
+            let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+            let unrevealed = !metadata.cancelled && metadata.last_bid == 0;
+            if unrevealed && sealed_bid.unrevealed_bids == UnrevealedBids::Forfeit {
+                return Err(AuctionError::UnrevealedBidForfeited.into());
+            }
+        }
+    }
+
     // Confirm we're looking at the real SPL account for this bidder.
     let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
     if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
@@ -221,7 +296,14 @@ pub fn cancel_bid(
 
     // Update Auction
 
-    if auction.state != AuctionState::Ended {
+    // Losing bids set the price of a second price auction, and the pages of a bid book are settled
+    // from the bids on them, so they stay put once bidding is over.
+    let settled = matches!(
+        auction.bid_state,
+        BidState::SecondPriceAuction { .. } | BidState::BidBook { .. }
+    ) && auction.ended(clock.unix_timestamp)?;
+
+    if auction.state != AuctionState::Ended && !settled {
         // Once ended we want uncancelled bids to retain it's pre-ending count
         assert_derivation(
             program_id,
@@ -245,9 +327,63 @@ pub fn cancel_bid(
         auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
         // Only cancel the bid if the auction has not ended yet
-        auction.bid_state.cancel_bid(*accounts.bidder.key);
+        match bid_page {
+            Some(bid_page) => {
+                if !already_cancelled {
+                    cancel_bid_on_book(program_id, &accounts, bid_page)?;
+                    auction.bid_state.cancel_bid(*accounts.bidder.key)?;
+                }
+            }
+            None => {
+                auction.bid_state.cancel_bid(*accounts.bidder.key);
+            }
+        }
         auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
     }
 
     Ok(())
 }
+
+/// Take the bid off its page, and take the page out of the list once it is empty so that the pages
+/// either side of it meet again. Page 0 always stays, as the list starts there.
+fn cancel_bid_on_book(
+    program_id: &Pubkey,
+    accounts: &Accounts,
+    mut bid_page: BidPage,
+) -> ProgramResult {
+    let bid_page_info = accounts.bid_page.ok_or(AuctionError::InvalidBidPage)?;
+    if !bid_page.cancel_bid(accounts.bidder.key) {
+        return Err(AuctionError::InvalidBidPage.into());
+    }
+
+    if bid_page.bids.is_empty() && bid_page.index != 0 {
+        if let Some(index) = bid_page.prev {
+            let mut prev_bid_page = assert_linked_bid_page(
+                program_id,
+                accounts.auction,
+                accounts.prev_bid_page,
+                index,
+            )?;
+            prev_bid_page.next = bid_page.next;
+            prev_bid_page.serialize(&mut *accounts.prev_bid_page.unwrap().data.borrow_mut())?;
+        }
+
+        if let Some(index) = bid_page.next {
+            let mut next_bid_page = assert_linked_bid_page(
+                program_id,
+                accounts.auction,
+                accounts.next_bid_page,
+                index,
+            )?;
+            next_bid_page.prev = bid_page.prev;
+            next_bid_page.serialize(&mut *accounts.next_bid_page.unwrap().data.borrow_mut())?;
+        }
+
+        bid_page.prev = None;
+        bid_page.next = None;
+    }
+
+    bid_page.serialize(&mut *bid_page_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,9 +1,19 @@
 //! Claim bid winnings into a target SPL account, only the authorised key can do this, though the
 //! target can be any SPL account.
+//!
+//! Besides winning bids, this claims the deposits of sealed bids that were never revealed when the
+//! auction keeps them.
+//!
+//! On a second price auction only the price the winner owes is claimed, and the rest of their bid
+//! is refunded to their own token account.
+//!
+//! Winners of a bid book are known once SettleBidPage has copied them onto the auction.
 
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    processor::{
+        AuctionData, AuctionDataExtended, BidState, BidderMetadata, BidderPot, UnrevealedBids,
+    },
     utils::{
         assert_derivation, assert_initialized, assert_owned_by, assert_signer,
         assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
@@ -26,6 +36,7 @@ use {
         sysvar::{clock::Clock, Sysvar},
     },
     spl_token::state::Account,
+    std::cmp,
 };
 
 #[repr(C)]
@@ -45,6 +56,8 @@ struct Accounts<'a, 'b: 'a> {
     clock_sysvar: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
     auction_extended: Option<&'a AccountInfo<'b>>,
+    bidder_meta: Option<&'a AccountInfo<'b>>,
+    bidder_token: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -63,6 +76,8 @@ fn parse_accounts<'a, 'b: 'a>(
         clock_sysvar: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
         auction_extended: next_account_info(account_iter).ok(),
+        bidder_meta: next_account_info(account_iter).ok(),
+        bidder_token: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
@@ -77,6 +92,14 @@ fn parse_accounts<'a, 'b: 'a>(
         assert_owned_by(auction_extended, program_id)?;
     }
 
+    if let Some(bidder_meta) = accounts.bidder_meta {
+        assert_owned_by(bidder_meta, program_id)?;
+    }
+
+    if let Some(bidder_token) = accounts.bidder_token {
+        assert_owned_by(bidder_token, &spl_token::id())?;
+    }
+
     if *accounts.token_program.key != spl_token::id() {
         return Err(AuctionError::InvalidTokenProgram.into());
     }
@@ -124,15 +147,7 @@ pub fn claim_bid(
         return Err(AuctionError::InvalidAuthority.into());
     }
 
-    // User must have won the auction in order to claim their funds. Check early as the rest of the
-    // checks will be for nothing otherwise.
-    let bid_index = auction.is_winner(accounts.bidder.key);
-    if bid_index.is_none() {
-        msg!("User {:?} is not winner", accounts.bidder.key);
-        return Err(AuctionError::InvalidState.into());
-    }
-
-    let instant_sale_price = accounts.auction_extended.and_then(|info| {
+    let auction_extended = accounts.auction_extended.and_then(|info| {
         assert_derivation(
             program_id,
             info,
@@ -145,13 +160,54 @@ pub fn claim_bid(
         )
         .ok()?;
 
-        AuctionDataExtended::from_account_info(info)
-            .ok()?
-            .instant_sale_price
+        AuctionDataExtended::from_account_info(info).ok()
     });
+    let instant_sale_price = auction_extended
+        .as_ref()
+        .and_then(|auction_extended| auction_extended.instant_sale_price);
+
+    // A sealed bid that was never revealed loses its deposit once the auction is over, if the
+    // auction keeps them.
+    let forfeited = match (
+        auction_extended.and_then(|auction_extended| auction_extended.sealed_bid),
+        accounts.bidder_meta,
+    ) {
+        (Some(sealed_bid), Some(bidder_meta)) => {
+            assert_derivation(
+                program_id,
+                bidder_meta,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    accounts.auction.key.as_ref(),
+                    accounts.bidder.key.as_ref(),
+                    "metadata".as_bytes(),
+                ],
+            )?;
+            let metadata = BidderMetadata::from_account_info(bidder_meta)?;
+            sealed_bid.unrevealed_bids == UnrevealedBids::Forfeit
+                && auction.ended(clock.unix_timestamp)?
+                && !metadata.cancelled
+                && metadata.last_bid == 0
+        }
+        _ => false,
+    };
 
-    // Auction either must have ended or bidder pay instant_sale_price
-    if !auction.ended(clock.unix_timestamp)? {
+    // User must have won the auction in order to claim their funds. Check early as the rest of the
+    // checks will be for nothing otherwise.
+    let bid_index = auction.is_winner(accounts.bidder.key);
+    if bid_index.is_none() && !auction.bid_state.winners_settled() {
+        return Err(AuctionError::BidPageNotSettled.into());
+    }
+    if bid_index.is_none() && !forfeited {
+        msg!("User {:?} is not winner", accounts.bidder.key);
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    // Auction either must have ended or bidder pay instant_sale_price, which every dutch auction
+    // winner does.
+    let dutch_auction = matches!(auction.bid_state, BidState::DutchAuction { .. });
+    if !auction.ended(clock.unix_timestamp)? && !dutch_auction {
         match instant_sale_price {
             Some(instant_sale_price)
                 if auction.bid_state.amount(bid_index.unwrap()) < instant_sale_price =>
@@ -197,6 +253,14 @@ pub fn claim_bid(
         return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
     }
 
+    // Winners of a second price auction only owe the price it settled at.
+    let payment = match (&auction.bid_state, bid_index) {
+        (BidState::SecondPriceAuction { .. }, Some(index)) => {
+            cmp::min(auction.payment_amount(index), actual_account.amount)
+        }
+        _ => actual_account.amount,
+    };
+
     // Transfer SPL bid balance back to the user.
     spl_token_transfer(TokenTransferParams {
         source: accounts.bidder_pot_token.clone(),
@@ -204,9 +268,32 @@ pub fn claim_bid(
         authority: accounts.auction.clone(),
         authority_signer_seeds: auction_seeds,
         token_program: accounts.token_program.clone(),
-        amount: actual_account.amount,
+        amount: payment,
     })?;
 
+    // Whatever was bid above that goes back to the bidder.
+    let refund = actual_account.amount - payment;
+    if refund > 0 {
+        let bidder_token = accounts
+            .bidder_token
+            .ok_or(AuctionError::InvalidRefundAccount)?;
+        let bidder_token_account: Account = assert_initialized(bidder_token)?;
+        if bidder_token_account.owner != *accounts.bidder.key
+            || bidder_token_account.mint != auction.token_mint
+        {
+            return Err(AuctionError::InvalidRefundAccount.into());
+        }
+
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_pot_token.clone(),
+            destination: bidder_token.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: refund,
+        })?;
+    }
+
     bidder_pot.emptied = true;
     bidder_pot.serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
 
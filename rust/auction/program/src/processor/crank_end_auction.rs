@@ -0,0 +1,116 @@
+//! Ends an auction once bidding is over, without the authority. The auction must have an end time,
+//! and only ends after it and the gap after the last bid have passed. A blinded price floor can't
+//! be revealed here, so an auction that has one only ends after the reveal deadline set in its
+//! BlindedFloorSettings, and then goes ahead the way they say.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionState, PriceFloor, UnrevealedFloor},
+    utils::{assert_derivation, assert_owned_by},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CrankEndAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    Ok(accounts)
+}
+
+pub fn crank_end_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: CrankEndAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing CrankEndAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    if auction.state == AuctionState::Ended {
+        return Err(AuctionError::AuctionTransitionInvalid.into());
+    }
+
+    // Without an end time only the authority can end the auction.
+    if !auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::AuctionNotOver.into());
+    }
+
+    if let PriceFloor::BlindedPrice(_) = auction.price_floor {
+        let blinded_floor = auction_extended
+            .blinded_floor
+            .ok_or(AuctionError::MustReveal)?;
+        if !blinded_floor.is_past_deadline(&auction, clock.unix_timestamp)? {
+            return Err(AuctionError::RevealDeadlineNotPassed.into());
+        }
+
+        match blinded_floor.unrevealed_floor {
+            UnrevealedFloor::NoFloor => auction.price_floor = PriceFloor::None([0; 32]),
+            UnrevealedFloor::RefundBids => auction.bid_state.clear_winners(),
+        }
+    }
+
+    AuctionData {
+        state: auction.state.end()?,
+        ..auction
+    }
+    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use mem::size_of;
 use crate::{
     errors::AuctionError,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid, BidState, PriceFloor,
-        WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
+        AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid, BidState,
+        BlindedFloorSettings, DutchAuctionSchedule, PriceFloor, SealedBidSettings, WinnerLimit,
+        BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
     },
     utils::{assert_derivation, assert_owned_by, create_or_allocate_account_raw},
     EXTENDED, PREFIX,
@@ -46,6 +47,22 @@ pub struct CreateAuctionArgs {
     pub gap_tick_size_percentage: Option<u8>,
 }
 
+/// Settings that turn the auction into something other than a plain english auction, only
+/// available through CreateAuctionV3. The default is a plain english auction.
+#[derive(Clone, Default)]
+pub struct AuctionSettings {
+    /// Price schedule of a dutch auction. See DutchAuctionSchedule.
+    pub dutch_auction: Option<DutchAuctionSchedule>,
+    /// Reveal settings of a sealed bid auction. See SealedBidSettings.
+    pub sealed_bid: Option<SealedBidSettings>,
+    /// Settle every winner at the second price.
+    pub second_price: bool,
+    /// Keep bids on BidPage accounts. See BidState::BidBook.
+    pub bid_book: bool,
+    /// Reveal deadline of a blinded price floor. See BlindedFloorSettings.
+    pub blinded_floor: Option<BlindedFloorSettings>,
+}
+
 struct Accounts<'a, 'b: 'a> {
     auction: &'a AccountInfo<'b>,
     auction_extended: &'a AccountInfo<'b>,
@@ -75,9 +92,17 @@ pub fn create_auction(
     args: CreateAuctionArgs,
     instant_sale_price: Option<u64>,
     name: Option<AuctionName>,
+    settings: AuctionSettings,
 ) -> ProgramResult {
     msg!("+ Processing CreateAuction");
     let accounts = parse_accounts(program_id, accounts)?;
+    let AuctionSettings {
+        dutch_auction,
+        sealed_bid,
+        second_price,
+        bid_book,
+        blinded_floor,
+    } = settings;
 
     let auction_path = [
         PREFIX.as_bytes(),
@@ -91,19 +116,85 @@ pub fn create_auction(
     if auction_key != *accounts.auction.key {
         return Err(AuctionError::InvalidAuctionAccount.into());
     }
-    // The data must be large enough to hold at least the number of winners.
+    // The data must be large enough to hold at least the number of winners. A bid book keeps its
+    // bids on pages and only copies the winners here, with one more bid's worth of room for the
+    // counters next to them.
     let auction_size = match args.winners {
+        WinnerLimit::Capped(n) if bid_book => {
+            mem::size_of::<Bid>() * (n + 1) + BASE_AUCTION_DATA_SIZE
+        }
         WinnerLimit::Capped(n) => {
             mem::size_of::<Bid>() * BidState::max_array_size_for(n) + BASE_AUCTION_DATA_SIZE
         }
         WinnerLimit::Unlimited(_) => BASE_AUCTION_DATA_SIZE,
     };
 
-    let bid_state = match args.winners {
-        WinnerLimit::Capped(n) => BidState::new_english(n),
-        WinnerLimit::Unlimited(_) => BidState::new_open_edition(),
+    // Winners pay the same price, so nobody may buy their lot outright at their own bid.
+    if second_price && instant_sale_price.is_some() {
+        return Err(AuctionError::InvalidSecondPriceSettings.into());
+    }
+
+    // Pages only know where a bid goes relative to the bids around it, so nothing may depend on
+    // the bid it beats or sell early.
+    if bid_book && (instant_sale_price.is_some() || args.gap_tick_size_percentage.is_some()) {
+        return Err(AuctionError::InvalidBidBookSettings.into());
+    }
+
+    let bid_state = match (args.winners, &dutch_auction, &sealed_bid) {
+        (WinnerLimit::Capped(n), None, None) if bid_book && !second_price => {
+            BidState::new_bid_book(n)
+        }
+        (WinnerLimit::Capped(n), None, None) if second_price => BidState::new_second_price(n),
+        (WinnerLimit::Capped(n), None, None) => BidState::new_english(n),
+        (WinnerLimit::Capped(n), Some(schedule), None) => {
+            // The price is set by the schedule, so nothing else may change what a bid has to be.
+            if instant_sale_price.is_some()
+                || args.tick_size.is_some()
+                || args.gap_tick_size_percentage.is_some()
+            {
+                return Err(AuctionError::InvalidDutchAuctionSchedule.into());
+            }
+            schedule.assert_valid()?;
+            BidState::new_dutch(n)
+        }
+        (WinnerLimit::Capped(n), None, Some(settings)) => {
+            // Bidding closes at a fixed time so that every bid can be revealed afterwards, nothing
+            // may sell early or push the end back.
+            if args.end_auction_at.is_none()
+                || args.end_auction_gap.is_some()
+                || instant_sale_price.is_some()
+                || settings.reveal_period <= 0
+            {
+                return Err(AuctionError::InvalidSealedBidSettings.into());
+            }
+            BidState::new_sealed_bid(n)
+        }
+        (WinnerLimit::Unlimited(_), None, None) => BidState::new_open_edition(),
+        (WinnerLimit::Unlimited(_), Some(_), None) => {
+            return Err(AuctionError::InvalidDutchAuctionSchedule.into())
+        }
+        (_, _, Some(_)) => return Err(AuctionError::InvalidSealedBidSettings.into()),
     };
 
+    if second_price && !matches!(bid_state, BidState::SecondPriceAuction { .. }) {
+        return Err(AuctionError::InvalidSecondPriceSettings.into());
+    }
+
+    if bid_book && !matches!(bid_state, BidState::BidBook { .. }) {
+        return Err(AuctionError::InvalidBidBookSettings.into());
+    }
+
+    // The deadline runs from the end of bidding, and there is only a floor to reveal if it was
+    // blinded.
+    if let Some(settings) = &blinded_floor {
+        if !matches!(args.price_floor, PriceFloor::BlindedPrice(_))
+            || args.end_auction_at.is_none()
+            || settings.reveal_deadline <= 0
+        {
+            return Err(AuctionError::InvalidBlindedFloorSettings.into());
+        }
+    }
+
     if let Some(gap_tick) = args.gap_tick_size_percentage {
         if gap_tick > 100 {
             return Err(AuctionError::InvalidGapTickSizePercentage.into());
@@ -160,6 +251,10 @@ pub fn create_auction(
         gap_tick_size_percentage: args.gap_tick_size_percentage,
         instant_sale_price,
         name,
+        dutch_auction,
+        started_at: None,
+        sealed_bid,
+        blinded_floor,
     }
     .serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
@@ -95,5 +95,6 @@ pub fn create_auction_v2(
         },
         args.instant_sale_price,
         args.name,
+        AuctionSettings::default(),
     )
 }
@@ -0,0 +1,84 @@
+use crate::processor::{
+    create_auction::*, AuctionName, BlindedFloorSettings, DutchAuctionSchedule, PriceFloor,
+    SealedBidSettings, WinnerLimit,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult, pubkey::Pubkey,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CreateAuctionArgsV3 {
+    /// How many winners are allowed for this auction. See AuctionData.
+    pub winners: WinnerLimit,
+    /// End time is the cut-off point that the auction is forced to end by. See AuctionData.
+    pub end_auction_at: Option<UnixTimestamp>,
+    /// Gap time is how much time after the previous bid where the auction ends. See AuctionData.
+    pub end_auction_gap: Option<UnixTimestamp>,
+    /// Token mint for the SPL token used for bidding.
+    pub token_mint: Pubkey,
+    /// Authority
+    pub authority: Pubkey,
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// Set a price floor.
+    pub price_floor: PriceFloor,
+    /// Add a tick size increment
+    pub tick_size: Option<u64>,
+    /// Add a minimum percentage increase each bid must meet.
+    pub gap_tick_size_percentage: Option<u8>,
+    /// Add a instant sale price.
+    pub instant_sale_price: Option<u64>,
+    /// Auction name
+    pub name: Option<AuctionName>,
+    /// Price schedule, turns the auction into a dutch auction where the price falls until someone
+    /// bids. See DutchAuctionSchedule.
+    pub dutch_auction: Option<DutchAuctionSchedule>,
+    /// Reveal settings, turns the auction into a sealed bid auction where bids stay hidden until
+    /// bidding closes. See SealedBidSettings.
+    pub sealed_bid: Option<SealedBidSettings>,
+    /// Settle at the second price, every winner pays the highest losing bid or the price floor
+    /// rather than their own bid. Only available on capped english auctions.
+    pub second_price: bool,
+    /// Keep bids on BidPage accounts instead of the auction account, so the number of winners
+    /// isn't limited by its size. Only available on english auctions. See BidState::BidBook.
+    pub bid_book: bool,
+    /// Reveal deadline of a blinded price floor, after which anyone can end the auction without
+    /// it. See BlindedFloorSettings.
+    pub blinded_floor: Option<BlindedFloorSettings>,
+}
+
+pub fn create_auction_v3(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateAuctionArgsV3,
+) -> ProgramResult {
+    create_auction(
+        program_id,
+        accounts,
+        CreateAuctionArgs {
+            winners: args.winners,
+            end_auction_at: args.end_auction_at,
+            end_auction_gap: args.end_auction_gap,
+            token_mint: args.token_mint,
+            authority: args.authority,
+            resource: args.resource,
+            price_floor: args.price_floor,
+            tick_size: args.tick_size,
+            gap_tick_size_percentage: args.gap_tick_size_percentage,
+        },
+        args.instant_sale_price,
+        args.name,
+        AuctionSettings {
+            dutch_auction: args.dutch_auction,
+            sealed_bid: args.sealed_bid,
+            second_price: args.second_price,
+            bid_book: args.bid_book,
+            blinded_floor: args.blinded_floor,
+        },
+    )
+}
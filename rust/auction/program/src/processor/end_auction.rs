@@ -1,8 +1,10 @@
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionState, Bid, BidState, PriceFloor, WinnerLimit},
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, PriceFloor, WinnerLimit,
+    },
     utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
-    PREFIX,
+    EXTENDED, PREFIX,
 };
 
 use {
@@ -37,6 +39,7 @@ struct Accounts<'a, 'b: 'a> {
     authority: &'a AccountInfo<'b>,
     auction: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -48,6 +51,7 @@ fn parse_accounts<'a, 'b: 'a>(
         authority: next_account_info(account_iter)?,
         auction: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter).ok(),
     };
     assert_owned_by(accounts.auction, program_id)?;
     assert_signer(accounts.authority)?;
@@ -100,13 +104,41 @@ pub fn end_auction<'a, 'b: 'a>(
         return Err(AuctionError::InvalidAuthority.into());
     }
 
+    // A blinded floor still has to be revealed once an auction with an end time closes, until the
+    // deadline passes and anyone can end the auction without it.
+    let revealing = matches!(auction.price_floor, PriceFloor::BlindedPrice(_))
+        && auction.state != AuctionState::Ended
+        && auction.ended(clock.unix_timestamp)?;
+
     // As long as it hasn't already ended.
-    if auction.ended_at.is_some() {
+    if auction.ended_at.is_some() && !revealing {
         return Err(AuctionError::AuctionTransitionInvalid.into());
     }
 
+    if revealing {
+        let auction_extended_info = accounts
+            .auction_extended
+            .ok_or(AuctionError::AuctionExtendedMissing)?;
+        assert_derivation(
+            program_id,
+            auction_extended_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                EXTENDED.as_bytes(),
+            ],
+        )?;
+        let auction_extended = AuctionDataExtended::from_account_info(auction_extended_info)?;
+        if let Some(blinded_floor) = auction_extended.blinded_floor {
+            if blinded_floor.is_past_deadline(&auction, clock.unix_timestamp)? {
+                return Err(AuctionError::RevealDeadlinePassed.into());
+            }
+        }
+    }
+
     AuctionData {
-        ended_at: Some(clock.unix_timestamp),
+        ended_at: Some(auction.ended_at.unwrap_or(clock.unix_timestamp)),
         state: auction.state.end()?,
         price_floor: reveal(auction.price_floor, args.reveal)?,
         ..auction
@@ -9,20 +9,25 @@
 //!
 //! A few solutions come to mind: don't allow cancelling bids, and simply prune all bids that
 //! are not winning bids from the state.
+//!
+//! Bid books keep every bid, on pages that the bidder points at. The bid goes onto the page it is
+//! given as long as it ranks correctly against the pages either side, and a full page is split in
+//! two so that no page ever has to move more than its own bids around.
 
 use borsh::try_to_vec_with_schema;
 
 use crate::{
     errors::AuctionError,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionState, Bid, BidderMetadata, BidderPot, PriceFloor,
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidPage, BidState, BidderMetadata,
+        BidderPot, PriceFloor, BID_PAGE_CAPACITY, BID_PAGE_LEN,
     },
     utils::{
-        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
-        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
-        TokenTransferParams,
+        assert_bid_page, assert_derivation, assert_initialized, assert_linked_bid_page,
+        assert_owned_by, assert_signer, assert_token_program_matches_package,
+        create_or_allocate_account_raw, spl_token_transfer, TokenTransferParams,
     },
-    EXTENDED, PREFIX,
+    BID_PAGE, EXTENDED, PREFIX,
 };
 
 use super::BIDDER_METADATA_LEN;
@@ -72,6 +77,10 @@ struct Accounts<'a, 'b: 'a> {
     system: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
     transfer_authority: &'a AccountInfo<'b>,
+    bid_page: Option<&'a AccountInfo<'b>>,
+    prev_bid_page: Option<&'a AccountInfo<'b>>,
+    next_bid_page: Option<&'a AccountInfo<'b>>,
+    new_bid_page: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -94,6 +103,10 @@ fn parse_accounts<'a, 'b: 'a>(
         rent: next_account_info(account_iter)?,
         system: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
+        bid_page: next_account_info(account_iter).ok(),
+        prev_bid_page: next_account_info(account_iter).ok(),
+        next_bid_page: next_account_info(account_iter).ok(),
+        new_bid_page: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
@@ -143,6 +156,12 @@ pub fn place_bid<'r, 'b: 'r>(
         msg!("Auction ended!");
         return Ok(());
     }
+
+    // Sealed bid auctions only take bids through PlaceSealedBid.
+    if let BidState::SealedBidAuction { .. } = auction.bid_state {
+        return Err(AuctionError::SealedBidRequired.into());
+    }
+
     // Derive Metadata key and load it.
     let metadata_bump = assert_derivation(
         program_id,
@@ -282,8 +301,26 @@ pub fn place_bid<'r, 'b: 'r>(
     auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
     let mut bid_price = args.amount;
+    let mut instant_sale_price = auction_extended.instant_sale_price;
+
+    // A dutch auction only takes bids at its current price, which sells a lot straight away just
+    // like an instant sale does.
+    if let BidState::DutchAuction { .. } = auction.bid_state {
+        let current_price = auction_extended
+            .dutch_auction_price(clock.unix_timestamp)
+            .ok_or(AuctionError::InvalidState)?;
+        if args.amount < current_price {
+            msg!(
+                "Bid {:?} is below the current price of {:?}",
+                args.amount,
+                current_price
+            );
+            return Err(AuctionError::BidTooSmall.into());
+        }
+        instant_sale_price = Some(current_price);
+    }
 
-    if let Some(instant_sale_price) = auction_extended.instant_sale_price {
+    if let Some(instant_sale_price) = instant_sale_price {
         if args.amount > instant_sale_price {
             msg!("Received amount is more than instant_sale_price so it was reduced to instant_sale_price - {:?}", instant_sale_price);
             bid_price = instant_sale_price;
@@ -313,13 +350,23 @@ pub fn place_bid<'r, 'b: 'r>(
 
     // Serialize new Auction State
     auction.last_bid = Some(clock.unix_timestamp);
-    auction.place_bid(
-        Bid(*accounts.bidder.key, bid_price),
-        auction_extended.tick_size,
-        auction_extended.gap_tick_size_percentage,
-        clock.unix_timestamp,
-        auction_extended.instant_sale_price,
-    )?;
+    if let BidState::BidBook { .. } = auction.bid_state {
+        place_bid_on_book(
+            program_id,
+            &accounts,
+            &mut auction,
+            Bid(*accounts.bidder.key, bid_price),
+            auction_extended.tick_size,
+        )?;
+    } else {
+        auction.place_bid(
+            Bid(*accounts.bidder.key, bid_price),
+            auction_extended.tick_size,
+            auction_extended.gap_tick_size_percentage,
+            clock.unix_timestamp,
+            instant_sale_price,
+        )?;
+    }
     auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
 
     // Update latest metadata with results from the bid.
@@ -334,3 +381,126 @@ pub fn place_bid<'r, 'b: 'r>(
 
     Ok(())
 }
+
+fn place_bid_on_book(
+    program_id: &Pubkey,
+    accounts: &Accounts,
+    auction: &mut AuctionData,
+    bid: Bid,
+    tick_size: Option<u64>,
+) -> ProgramResult {
+    BidState::assert_valid_tick_size_bid(&bid, tick_size)?;
+    let minimum = match auction.price_floor {
+        PriceFloor::MinimumPrice(min) => min[0],
+        _ => 0,
+    };
+    if bid.1 < minimum {
+        return Err(AuctionError::BidTooSmall.into());
+    }
+
+    let (len, pages) = match auction.bid_state {
+        BidState::BidBook {
+            ref mut len,
+            ref mut pages,
+            ..
+        } => (len, pages),
+        _ => return Err(AuctionError::InvalidState.into()),
+    };
+    let bid_page_info = accounts.bid_page.ok_or(AuctionError::InvalidBidPage)?;
+
+    let (mut bid_page, next_bid_page) = if *pages == 0 {
+        // The first bid opens page 0.
+        create_bid_page(program_id, accounts, bid_page_info, 0)?;
+        *pages = 1;
+        let bid_page = BidPage {
+            auction: *accounts.auction.key,
+            index: 0,
+            prev: None,
+            next: None,
+            rank_offset: None,
+            bids: vec![],
+        };
+        (bid_page, None)
+    } else {
+        let bid_page = assert_bid_page(program_id, accounts.auction, bid_page_info)?;
+        let prev_bid_page = match bid_page.prev {
+            Some(index) => Some(assert_linked_bid_page(
+                program_id,
+                accounts.auction,
+                accounts.prev_bid_page,
+                index,
+            )?),
+            None => None,
+        };
+        let next_bid_page = match bid_page.next {
+            Some(index) => Some(assert_linked_bid_page(
+                program_id,
+                accounts.auction,
+                accounts.next_bid_page,
+                index,
+            )?),
+            None => None,
+        };
+        bid_page.assert_valid_placement(bid.1, prev_bid_page.as_ref(), next_bid_page.as_ref())?;
+        (bid_page, next_bid_page)
+    };
+
+    bid_page.place_bid(bid);
+
+    if bid_page.bids.len() > BID_PAGE_CAPACITY {
+        let new_bid_page_info = accounts.new_bid_page.ok_or(AuctionError::InvalidBidPage)?;
+        create_bid_page(program_id, accounts, new_bid_page_info, *pages)?;
+        let new_bid_page = bid_page.split(*pages);
+
+        if let Some(mut next_bid_page) = next_bid_page {
+            next_bid_page.prev = Some(new_bid_page.index);
+            next_bid_page.serialize(&mut *accounts.next_bid_page.unwrap().data.borrow_mut())?;
+        }
+        new_bid_page.serialize(&mut *new_bid_page_info.data.borrow_mut())?;
+
+        *pages = pages
+            .checked_add(1)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+    }
+    bid_page.serialize(&mut *bid_page_info.data.borrow_mut())?;
+
+    *len = len
+        .checked_add(1)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+
+    Ok(())
+}
+
+fn create_bid_page<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &Accounts<'a, 'b>,
+    bid_page: &'a AccountInfo<'b>,
+    index: u32,
+) -> ProgramResult {
+    let index_bytes = index.to_le_bytes();
+    let bid_page_seeds = [
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.auction.key.as_ref(),
+        BID_PAGE.as_bytes(),
+        &index_bytes,
+    ];
+    let bump = assert_derivation(program_id, bid_page, &bid_page_seeds)?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        bid_page,
+        accounts.rent,
+        accounts.system,
+        accounts.payer,
+        BID_PAGE_LEN,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            BID_PAGE.as_bytes(),
+            &index_bytes,
+            &[bump],
+        ],
+    )
+}
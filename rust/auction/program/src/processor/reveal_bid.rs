@@ -0,0 +1,266 @@
+//! Reveals a sealed bid once bidding has closed. The revealed amount has to hash to the
+//! commitment made with PlaceSealedBid, after which it is ranked against the other revealed bids
+//! just like a bid on an English auction, and the part of the deposit above it is refunded.
+//!
+//! Tick size and gap checks are left out here. Amounts are hidden while bids are committed, so a
+//! bidder can't know whether theirs would pass, and a bid that fails to reveal counts as unrevealed.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, BidderMetadata, BidderPot,
+        SealedBid,
+    },
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, spl_token_transfer, TokenTransferParams,
+    },
+    EXTENDED, PREFIX, SEALED_BID,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    spl_token::state::Account,
+};
+
+/// Arguments for the RevealBid instruction discriminant .
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct RevealBidArgs {
+    /// Resource being bid on.
+    pub resource: Pubkey,
+    /// Size of the bid, hashed with the salt to get the commitment, see SealedBid::commitment.
+    pub amount: u64,
+    /// Salt that keeps the commitment from being guessed.
+    pub salt: u64,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    sealed_bid: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        sealed_bid: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.sealed_bid, program_id)?;
+    assert_owned_by(accounts.mint, &spl_token::id())?;
+    assert_owned_by(accounts.bidder_token, &spl_token::id())?;
+    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+    assert_signer(accounts.bidder)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(AuctionError::InvalidTokenProgram.into());
+    }
+
+    Ok(accounts)
+}
+
+pub fn reveal_bid(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RevealBidArgs,
+) -> ProgramResult {
+    msg!("+ Processing RevealBid");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+
+    // Load the clock, used for various auction timing.
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    // Bids can only be revealed on a sealed bid auction that hasn't ended yet.
+    if !matches!(auction.bid_state, BidState::SealedBidAuction { .. })
+        || auction.state != AuctionState::Started
+        || auction.ended(clock.unix_timestamp)?
+    {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    // The mint provided in this bid must match the one the auction was initialized with.
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    let sealed_bid_settings = auction_extended
+        .sealed_bid
+        .as_ref()
+        .ok_or(AuctionError::InvalidState)?;
+
+    // Revealing while bidding is open would let others bid just above.
+    if !sealed_bid_settings.is_revealing(auction.ended_at, clock.unix_timestamp)? {
+        return Err(AuctionError::RevealPeriodNotStarted.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.sealed_bid,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            SEALED_BID.as_bytes(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // Only a bid that is still standing and hasn't been revealed yet can be revealed.
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    if metadata.cancelled || metadata.last_bid != 0 {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    let sealed_bid = SealedBid::from_account_info(accounts.sealed_bid)?;
+    let reveal_hash = SealedBid::commitment(
+        accounts.auction.key,
+        accounts.bidder.key,
+        args.amount,
+        args.salt,
+    );
+    if reveal_hash != sealed_bid.commitment {
+        return Err(AuctionError::InvalidReveal.into());
+    }
+
+    if args.amount == 0 {
+        return Err(AuctionError::BidTooSmall.into());
+    }
+
+    // Confirm we're looking at the real SPL account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    // The deposit has to cover the bid.
+    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+    if actual_account.owner != *accounts.auction.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+    if actual_account.amount < args.amount {
+        msg!(
+            "Bid {:?} is more than the deposit of {:?}",
+            args.amount,
+            actual_account.amount
+        );
+        return Err(AuctionError::BalanceTooLow.into());
+    }
+
+    auction.place_bid(
+        Bid(*accounts.bidder.key, args.amount),
+        None,
+        None,
+        clock.unix_timestamp,
+        None,
+    )?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    // Refund whatever the deposit held above the bid.
+    let refund = actual_account.amount - args.amount;
+    if refund > 0 {
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_pot_token.clone(),
+            destination: accounts.bidder_token.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: refund,
+        })?;
+    }
+
+    BidderMetadata {
+        last_bid: args.amount,
+        last_bid_timestamp: clock.unix_timestamp,
+        ..metadata
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Settles a page of a bid book once bidding is over. Bids on a page are only ranked against the
+//! page itself, so each page records how many bids rank ahead of it, taken from the settled page
+//! before it. Pages are settled from the top of the book down, and the winning bids on each are
+//! copied onto the auction, where they can be looked up like those of an English auction.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionState, BidState},
+    utils::{assert_bid_page, assert_derivation, assert_linked_bid_page, assert_owned_by},
+    PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct SettleBidPageArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    bid_page: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    prev_bid_page: Option<&'a AccountInfo<'b>>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bid_page: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        prev_bid_page: next_account_info(account_iter).ok(),
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    Ok(accounts)
+}
+
+pub fn settle_bid_page<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: SettleBidPageArgs,
+) -> ProgramResult {
+    msg!("+ Processing SettleBidPage");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    // Pages can only be settled once no more bids can come in or go away.
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    if !matches!(auction.bid_state, BidState::BidBook { .. })
+        || (auction.state != AuctionState::Ended && !auction.ended(clock.unix_timestamp)?)
+    {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    let mut bid_page = assert_bid_page(program_id, accounts.auction, accounts.bid_page)?;
+    if !bid_page.is_linked() {
+        return Err(AuctionError::InvalidBidPage.into());
+    }
+
+    // Its winners would be copied onto the auction twice.
+    if bid_page.rank_offset.is_some() {
+        return Err(AuctionError::BidPageAlreadySettled.into());
+    }
+
+    bid_page.rank_offset = Some(match bid_page.prev {
+        None => 0,
+        Some(prev) => {
+            let prev_bid_page =
+                assert_linked_bid_page(program_id, accounts.auction, accounts.prev_bid_page, prev)?;
+            prev_bid_page
+                .rank_offset
+                .ok_or(AuctionError::BidPageNotSettled)?
+                .checked_add(prev_bid_page.bids.len() as u64)
+                .ok_or(AuctionError::NumericalOverflowError)?
+        }
+    });
+    bid_page.serialize(&mut *accounts.bid_page.data.borrow_mut())?;
+
+    auction.bid_state.add_winners_from_page(&bid_page)?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
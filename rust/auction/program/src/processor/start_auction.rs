@@ -1,8 +1,8 @@
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionState, Bid, BidState, WinnerLimit},
+    processor::{AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, WinnerLimit},
     utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
-    PREFIX,
+    EXTENDED, PREFIX,
 };
 
 use {
@@ -23,6 +23,7 @@ struct Accounts<'a, 'b: 'a> {
     authority: &'a AccountInfo<'b>,
     auction: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -34,9 +35,15 @@ fn parse_accounts<'a, 'b: 'a>(
         authority: next_account_info(account_iter)?,
         auction: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter).ok(),
     };
     assert_owned_by(accounts.auction, program_id)?;
     assert_signer(accounts.authority)?;
+
+    if let Some(auction_extended) = accounts.auction_extended {
+        assert_owned_by(auction_extended, program_id)?;
+    }
+
     Ok(accounts)
 }
 
@@ -76,7 +83,7 @@ pub fn start_auction<'a, 'b: 'a>(
     }
 
     // Calculate the relative end time.
-    let ended_at = if let Some(end_auction_at) = auction.end_auction_at {
+    let mut ended_at = if let Some(end_auction_at) = auction.end_auction_at {
         match clock.unix_timestamp.checked_add(end_auction_at) {
             Some(val) => Some(val),
             None => return Err(AuctionError::NumericalOverflowError.into()),
@@ -85,6 +92,41 @@ pub fn start_auction<'a, 'b: 'a>(
         None
     };
 
+    // Record the start so a dutch auction knows how far its price has fallen, and make room for
+    // the reveal period of a sealed bid auction after bidding closes.
+    match accounts.auction_extended {
+        Some(auction_extended_info) => {
+            assert_derivation(
+                program_id,
+                auction_extended_info,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    &args.resource.as_ref(),
+                    EXTENDED.as_bytes(),
+                ],
+            )?;
+
+            let mut auction_extended =
+                AuctionDataExtended::from_account_info(auction_extended_info)?;
+            auction_extended.started_at = Some(clock.unix_timestamp);
+            if let Some(sealed_bid) = &auction_extended.sealed_bid {
+                ended_at = ended_at
+                    .and_then(|end| end.checked_add(sealed_bid.reveal_period))
+                    .ok_or(AuctionError::NumericalOverflowError)
+                    .map(Some)?;
+            }
+            auction_extended.serialize(&mut *auction_extended_info.data.borrow_mut())?;
+        }
+        None => {
+            if let BidState::DutchAuction { .. } | BidState::SealedBidAuction { .. } =
+                auction.bid_state
+            {
+                return Err(AuctionError::AuctionExtendedMissing.into());
+            }
+        }
+    }
+
     AuctionData {
         ended_at,
         state: auction.state.start()?,
@@ -1,7 +1,7 @@
 use solana_program::program_pack::IsInitialized;
 
 use {
-    crate::errors::AuctionError,
+    crate::{errors::AuctionError, processor::BidPage, BID_PAGE, PREFIX},
     solana_program::{
         account_info::AccountInfo,
         entrypoint::ProgramResult,
@@ -77,6 +77,43 @@ pub fn assert_derivation(
     Ok(bump)
 }
 
+/// Load a page of the bid book of an auction, making sure it is the page derived for its index.
+pub fn assert_bid_page(
+    program_id: &Pubkey,
+    auction: &AccountInfo,
+    bid_page: &AccountInfo,
+) -> Result<BidPage, ProgramError> {
+    assert_owned_by(bid_page, program_id)?;
+    let page = BidPage::from_account_info(bid_page)?;
+    assert_derivation(
+        program_id,
+        bid_page,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction.key.as_ref(),
+            BID_PAGE.as_bytes(),
+            &page.index.to_le_bytes(),
+        ],
+    )?;
+    Ok(page)
+}
+
+/// Load the page a bid page links to, which has to be given when the link is there.
+pub fn assert_linked_bid_page(
+    program_id: &Pubkey,
+    auction: &AccountInfo,
+    bid_page: Option<&AccountInfo>,
+    index: u32,
+) -> Result<BidPage, ProgramError> {
+    let bid_page = bid_page.ok_or(AuctionError::InvalidBidPage)?;
+    let page = assert_bid_page(program_id, auction, bid_page)?;
+    if page.index != index {
+        return Err(AuctionError::InvalidBidPage.into());
+    }
+    Ok(page)
+}
+
 #[inline(always)]
 pub fn create_or_allocate_account_raw<'a>(
     program_id: Pubkey,
@@ -1,4 +1,6 @@
-use solana_program::{hash::Hash, program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program::{
+    clock::UnixTimestamp, hash::Hash, program_pack::Pack, pubkey::Pubkey, system_instruction,
+};
 use solana_program_test::*;
 use solana_sdk::{
     account::Account,
@@ -9,9 +11,12 @@ use solana_sdk::{
 use metaplex_auction::{
     instruction,
     processor::{
-        CancelBidArgs, ClaimBidArgs, CreateAuctionArgs, CreateAuctionArgsV2, EndAuctionArgs,
-        PlaceBidArgs, PriceFloor, StartAuctionArgs, WinnerLimit,
+        BlindedFloorSettings, CancelBidArgs, ClaimBidArgs, CrankEndAuctionArgs, CreateAuctionArgs,
+        CreateAuctionArgsV2, CreateAuctionArgsV3, DutchAuctionSchedule, EndAuctionArgs,
+        PlaceBidArgs, PlaceSealedBidArgs, PriceFloor, RevealBidArgs, SealedBidSettings,
+        SettleBidPageArgs, StartAuctionArgs, WinnerLimit,
     },
+    EXTENDED, PREFIX,
 };
 
 fn string_to_array(value: &str) -> Result<[u8; 32], TransportError> {
@@ -155,9 +160,38 @@ pub async fn create_auction(
     price_floor: PriceFloor,
     gap_tick_size_percentage: Option<u8>,
     tick_size: Option<u64>,
+    dutch_auction: Option<DutchAuctionSchedule>,
 ) -> Result<(), TransportError> {
     let transaction: Transaction;
-    if instant_sale_price.is_some() {
+    if dutch_auction.is_some() {
+        transaction = Transaction::new_signed_with_payer(
+            &[instruction::create_auction_instruction_v3(
+                *program_id,
+                payer.pubkey(),
+                CreateAuctionArgsV3 {
+                    authority: payer.pubkey(),
+                    end_auction_at: None,
+                    end_auction_gap: None,
+                    resource: *resource,
+                    token_mint: *mint_keypair,
+                    winners: WinnerLimit::Capped(max_winners),
+                    price_floor,
+                    gap_tick_size_percentage,
+                    tick_size,
+                    name: Some(string_to_array(name)?),
+                    instant_sale_price,
+                    dutch_auction,
+                    sealed_bid: None,
+                    second_price: false,
+                    bid_book: false,
+                    blinded_floor: None,
+                },
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            *recent_blockhash,
+        );
+    } else if instant_sale_price.is_some() {
         transaction = Transaction::new_signed_with_payer(
             &[instruction::create_auction_instruction_v2(
                 *program_id,
@@ -172,7 +206,7 @@ pub async fn create_auction(
                     price_floor,
                     gap_tick_size_percentage,
                     tick_size,
-                    name: string_to_array(name)?,
+                    name: Some(string_to_array(name)?),
                     instant_sale_price,
                 },
             )],
@@ -206,6 +240,135 @@ pub async fn create_auction(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn create_sealed_bid_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    end_auction_at: UnixTimestamp,
+    sealed_bid: SealedBidSettings,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: Some(end_auction_at),
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                dutch_auction: None,
+                sealed_bid: Some(sealed_bid),
+                second_price: false,
+                bid_book: false,
+                blinded_floor: None,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_second_price_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    price_floor: PriceFloor,
+    instant_sale_price: Option<u64>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor,
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price,
+                dutch_auction: None,
+                sealed_bid: None,
+                second_price: true,
+                bid_book: false,
+                blinded_floor: None,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_bid_book_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    price_floor: PriceFloor,
+    instant_sale_price: Option<u64>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor,
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price,
+                dutch_auction: None,
+                sealed_bid: None,
+                second_price: false,
+                bid_book: true,
+                blinded_floor: None,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 pub async fn end_auction(
     banks_client: &mut BanksClient,
     program_id: &Pubkey,
@@ -217,6 +380,7 @@ pub async fn end_auction(
         &[instruction::end_auction_instruction(
             *program_id,
             payer.pubkey(),
+            None,
             EndAuctionArgs {
                 resource: *resource,
                 reveal: None,
@@ -230,6 +394,108 @@ pub async fn end_auction(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn create_blinded_floor_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    end_auction_at: UnixTimestamp,
+    blinded_price: Hash,
+    blinded_floor: BlindedFloorSettings,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: Some(end_auction_at),
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::BlindedPrice(blinded_price),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                dutch_auction: None,
+                sealed_bid: None,
+                second_price: false,
+                bid_book: false,
+                blinded_floor: Some(blinded_floor),
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn end_auction_with_reveal(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    resource: &Pubkey,
+    reveal: (u64, u64),
+) -> Result<(), TransportError> {
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+        program_id,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::end_auction_instruction(
+            *program_id,
+            payer.pubkey(),
+            Some(auction_extended_pubkey),
+            EndAuctionArgs {
+                resource: *resource,
+                reveal: Some(reveal),
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn crank_end_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::crank_end_auction_instruction(
+            *program_id,
+            CrankEndAuctionArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 pub async fn start_auction(
     banks_client: &mut BanksClient,
     program_id: &Pubkey,
@@ -288,6 +554,120 @@ pub async fn place_bid(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn place_bid_on_book(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    transfer_authority: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    bid_page: u32,
+    prev_bid_page: Option<u32>,
+    next_bid_page: Option<u32>,
+    new_bid_page: u32,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::place_bid_on_book_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            transfer_authority.pubkey(),
+            payer.pubkey(),
+            bid_page,
+            prev_bid_page,
+            next_bid_page,
+            new_bid_page,
+            PlaceBidArgs {
+                amount,
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, transfer_authority, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn place_sealed_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    transfer_authority: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+    deposit: u64,
+    commitment: Hash,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::place_sealed_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            transfer_authority.pubkey(),
+            payer.pubkey(),
+            PlaceSealedBidArgs {
+                resource: *resource,
+                deposit,
+                commitment,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, transfer_authority, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn reveal_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    salt: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::reveal_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            RevealBidArgs {
+                resource: *resource,
+                amount,
+                salt,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn cancel_bid(
     banks_client: &mut BanksClient,
@@ -318,6 +698,68 @@ pub async fn cancel_bid(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn cancel_bid_on_book(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+    bid_page: u32,
+    prev_bid_page: Option<u32>,
+    next_bid_page: Option<u32>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::cancel_bid_on_book_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            bid_page,
+            prev_bid_page,
+            next_bid_page,
+            CancelBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn settle_bid_page(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    resource: &Pubkey,
+    bid_page: u32,
+    prev_bid_page: Option<u32>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::settle_bid_page_instruction(
+            *program_id,
+            bid_page,
+            prev_bid_page,
+            SettleBidPageArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 pub async fn approve(
     banks_client: &mut BanksClient,
     recent_blockhash: &Hash,
@@ -356,7 +798,18 @@ pub async fn claim_bid(
     seller: &Pubkey,
     resource: &Pubkey,
     mint: &Pubkey,
+    bidder_token: Option<&Pubkey>,
 ) -> Result<(), TransportError> {
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+        program_id,
+    );
+
     let transaction = Transaction::new_signed_with_payer(
         &[instruction::claim_bid_instruction(
             *program_id,
@@ -365,6 +818,8 @@ pub async fn claim_bid(
             bidder.pubkey(),
             bidder_spl_account.pubkey(),
             *mint,
+            Some(auction_extended_pubkey),
+            bidder_token.copied(),
             ClaimBidArgs {
                 resource: *resource,
             },
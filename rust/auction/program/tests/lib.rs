@@ -1,7 +1,13 @@
 #![allow(warnings)]
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{borsh::try_from_slice_unchecked, instruction::InstructionError};
+use solana_program::{
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    clock::{Clock, UnixTimestamp},
+    hash,
+    instruction::InstructionError,
+};
 use solana_program_test::*;
 use solana_sdk::program_pack::Pack;
 use solana_sdk::{
@@ -18,15 +24,88 @@ use metaplex_auction::{
     errors::AuctionError,
     instruction,
     processor::{
-        process_instruction, AuctionData, AuctionState, Bid, BidState, BidderPot, CancelBidArgs,
-        CreateAuctionArgs, PlaceBidArgs, PriceFloor, StartAuctionArgs, WinnerLimit,
+        process_instruction, AuctionData, AuctionState, Bid, BidPage, BidState, BidderMetadata,
+        BidderPot, BlindedFloorSettings, SealedBid, CancelBidArgs, CreateAuctionArgs, DutchAuctionSchedule, PlaceBidArgs,
+        PriceDecay, PriceFloor, SealedBidSettings, StartAuctionArgs, UnrevealedBids,
+        UnrevealedFloor, WinnerLimit,
     },
-    PREFIX,
+    BID_PAGE, PREFIX,
 };
 use std::mem;
 
 mod helpers;
 
+/// Generate bidders with tokens, and a pot token account for each of them on the auction.
+async fn create_bidders(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    auction_pubkey: &Pubkey,
+    mint: &Pubkey,
+    mint_manager: &Keypair,
+) -> Vec<(Keypair, Keypair, Pubkey)> {
+    let mut bidders = vec![];
+    for n in 0..5 {
+        // Bidder SPL Account, with Minted Tokens
+        let bidder = Keypair::new();
+        // PDA in the auction for the Bidder to deposit their funds to.
+        let auction_spl_pot = Keypair::new();
+
+        // Generate User SPL Wallet Account
+        helpers::create_token_account(
+            banks_client,
+            payer,
+            recent_blockhash,
+            &bidder,
+            mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        // Owner via pot PDA.
+        let (bid_pot_pubkey, pot_bump) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction_pubkey.as_ref(),
+                bidder.pubkey().as_ref(),
+            ],
+            program_id,
+        );
+
+        // Generate Auction SPL Pot to Transfer to.
+        helpers::create_token_account(
+            banks_client,
+            payer,
+            recent_blockhash,
+            &auction_spl_pot,
+            mint,
+            auction_pubkey,
+        )
+        .await
+        .unwrap();
+
+        // Mint Tokens
+        helpers::mint_tokens(
+            banks_client,
+            payer,
+            recent_blockhash,
+            mint,
+            &bidder.pubkey(),
+            mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+
+        bidders.push((bidder, auction_spl_pot, bid_pot_pubkey));
+    }
+
+    bidders
+}
+
 /// Initialize an auction with a random resource, and generate bidders with tokens that can be used
 /// for testing.
 async fn setup_auction(
@@ -36,6 +115,7 @@ async fn setup_auction(
     price_floor: PriceFloor,
     gap_tick_size_percentage: Option<u8>,
     tick_size: Option<u64>,
+    dutch_auction: Option<DutchAuctionSchedule>,
 ) -> (
     Pubkey,
     BanksClient,
@@ -80,6 +160,7 @@ async fn setup_auction(
         price_floor,
         gap_tick_size_percentage,
         tick_size,
+        dutch_auction,
     )
     .await
     .unwrap();
@@ -297,6 +378,7 @@ async fn test_correct_runs() {
             strategy.price_floor.clone(),
             Some(0),
             None,
+            None,
         )
         .await;
 
@@ -477,6 +559,7 @@ async fn test_correct_runs() {
                             &collection.pubkey(),
                             &resource,
                             &mint,
+                            None,
                         )
                         .await;
                         println!("{:?}", err);
@@ -683,6 +766,7 @@ async fn test_incorrect_runs() {
             strategy.price_floor.clone(),
             strategy.gap_tick_size_percentage,
             strategy.tick_size,
+            None,
         )
         .await;
 
@@ -732,6 +816,7 @@ async fn test_place_instant_sale_bid() {
         PriceFloor::None([0; 32]),
         Some(0),
         None,
+        None,
     )
     .await;
 
@@ -822,6 +907,7 @@ async fn test_all_bids_are_taken_by_instant_sale_price() {
         strategy.price_floor,
         Some(0),
         None,
+        None,
     )
     .await;
 
@@ -934,6 +1020,7 @@ async fn test_claim_bid_with_instant_sale_price() {
         PriceFloor::None([0; 32]),
         Some(0),
         None,
+        None,
     )
     .await;
 
@@ -990,6 +1077,7 @@ async fn test_claim_bid_with_instant_sale_price() {
         &collection.pubkey(),
         &resource,
         &mint,
+        None,
     )
     .await
     .unwrap();
@@ -1041,6 +1129,7 @@ async fn test_cancel_bid_with_instant_sale_price() {
         strategy.price_floor,
         Some(0),
         None,
+        None,
     )
     .await;
 
@@ -1129,3 +1218,2074 @@ async fn test_cancel_bid_with_instant_sale_price() {
         }
     }
 }
+
+/// Price stays at the start price for a whole day, so it doesn't move while the test runs.
+fn dutch_auction_schedule() -> DutchAuctionSchedule {
+    DutchAuctionSchedule {
+        start_price: 5000,
+        floor_price: 1000,
+        decay_duration: 86400,
+        decay: PriceDecay::Stepped(86400),
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_dutch_auction_first_bid_wins() {
+    let bid_price = 6000;
+
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        mint_authority,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(
+        true,
+        1,
+        None,
+        PriceFloor::None([0; 32]),
+        None,
+        None,
+        Some(dutch_auction_schedule()),
+    )
+    .await;
+
+    // Get balances pre bidding.
+    let pre_balance = (
+        helpers::get_token_balance(&mut banks_client, &bidders[0].0.pubkey()).await,
+        helpers::get_token_balance(&mut banks_client, &bidders[0].1.pubkey()).await,
+    );
+
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidders[0].0,
+        bid_price,
+    )
+    .await
+    .expect("approve");
+
+    // Bid above the current price, only the current price should be taken.
+    helpers::place_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        bid_price,
+    )
+    .await
+    .expect("place_bid");
+
+    let post_balance = (
+        helpers::get_token_balance(&mut banks_client, &bidders[0].0.pubkey()).await,
+        helpers::get_token_balance(&mut banks_client, &bidders[0].1.pubkey()).await,
+    );
+
+    assert_eq!(post_balance.0, pre_balance.0 - 5000);
+    assert_eq!(post_balance.1, pre_balance.1 + 5000);
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+
+    // The only lot is sold, so the auction is over.
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(
+        auction.bid_state,
+        BidState::DutchAuction {
+            bids: vec![Bid(bidders[0].0.pubkey(), 5000)],
+            max: 1,
+        }
+    );
+
+    // Later bidders are too late.
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidders[1].0,
+        bid_price,
+    )
+    .await
+    .expect("approve");
+
+    let err = helpers::place_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        bid_price,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidState as u32)
+        )
+    );
+
+    // The winner can be claimed straight away, like an instant sale.
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    helpers::claim_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let balance = helpers::get_token_balance(&mut banks_client, &collection.pubkey()).await;
+    assert_eq!(balance, 5000);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_dutch_auction_bid_below_current_price() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        mint_authority,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(
+        true,
+        1,
+        None,
+        PriceFloor::None([0; 32]),
+        None,
+        None,
+        Some(dutch_auction_schedule()),
+    )
+    .await;
+
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidders[0].0,
+        4000,
+    )
+    .await
+    .expect("approve");
+
+    let err = helpers::place_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        4000,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::BidTooSmall as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_dutch_auction_with_instant_sale_price() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let err = helpers::create_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        1,
+        "Some name",
+        Some(5000),
+        PriceFloor::None([0; 32]),
+        None,
+        None,
+        Some(dutch_auction_schedule()),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidDutchAuctionSchedule as u32)
+        )
+    );
+}
+
+/// Hash a sealed bid the way RevealBid checks it.
+fn seal_bid(auction: &Pubkey, bidder: &Pubkey, amount: u64, salt: u64) -> Hash {
+    SealedBid::commitment(auction, bidder, amount, salt)
+}
+
+/// Hash a blinded price floor the way EndAuction checks it.
+fn seal(amount: u64, salt: u64) -> Hash {
+    hash::hashv(&[&amount.to_be_bytes(), &salt.to_be_bytes()])
+}
+
+/// Move the clock forward, warping to a later slot leaves the timestamp where it was.
+async fn advance_clock(context: &mut ProgramTestContext, seconds: UnixTimestamp) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Bidding is open for 100 seconds after the start, followed by a reveal period of 1000 seconds.
+async fn setup_sealed_bid_auction(
+    unrevealed_bids: UnrevealedBids,
+) -> (
+    Pubkey,
+    ProgramTestContext,
+    Vec<(Keypair, Keypair, Pubkey)>,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+) {
+    let program_id = Pubkey::new_unique();
+    let program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let recent_blockhash = context.last_blockhash;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut context.banks_client, &context.payer, &recent_blockhash)
+            .await
+            .unwrap();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_sealed_bid_auction(
+        &mut context.banks_client,
+        &program_id,
+        &context.payer,
+        &recent_blockhash,
+        &resource,
+        &mint_keypair.pubkey(),
+        1,
+        100,
+        SealedBidSettings {
+            reveal_period: 1000,
+            unrevealed_bids,
+        },
+    )
+    .await
+    .unwrap();
+
+    let bidders = create_bidders(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &program_id,
+        &auction_pubkey,
+        &mint_keypair.pubkey(),
+        &mint_manager,
+    )
+    .await;
+
+    helpers::start_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    (
+        program_id,
+        context,
+        bidders,
+        resource,
+        mint_keypair.pubkey(),
+        auction_pubkey,
+    )
+}
+
+/// Approve a transfer authority for the deposit and commit to a sealed bid with it.
+async fn commit_sealed_bid(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    bidder: &(Keypair, Keypair, Pubkey),
+    resource: &Pubkey,
+    mint: &Pubkey,
+    deposit: u64,
+    commitment: Hash,
+) -> Result<(), TransportError> {
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &context.payer,
+        &transfer_authority.pubkey(),
+        &bidder.0,
+        deposit,
+    )
+    .await
+    .expect("approve");
+
+    helpers::place_sealed_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        program_id,
+        &context.payer,
+        &bidder.0,
+        &bidder.1,
+        &transfer_authority,
+        resource,
+        mint,
+        deposit,
+        commitment,
+    )
+    .await
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_sealed_bid_auction() {
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_sealed_bid_auction(UnrevealedBids::Refund).await;
+
+    // Each deposit covers the bid it hides.
+    let bids = [(3000, 5000, 11), (4000, 4000, 22), (2000, 2500, 33)];
+    for (bidder, (amount, deposit, salt)) in bidders.iter().zip(bids.iter()) {
+        commit_sealed_bid(
+            &mut context,
+            &program_id,
+            bidder,
+            &resource,
+            &mint,
+            *deposit,
+            seal_bid(&auction_pubkey, &bidder.0.pubkey(), *amount, *salt),
+        )
+        .await
+        .expect("place_sealed_bid");
+    }
+
+    for (bidder, (_, deposit, _)) in bidders.iter().zip(bids.iter()) {
+        let balance =
+            helpers::get_token_balance(&mut context.banks_client, &bidder.1.pubkey()).await;
+        assert_eq!(balance, *deposit);
+    }
+
+    // Nothing is known about the bids until they are revealed.
+    let auction: AuctionData = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction.bid_state,
+        BidState::SealedBidAuction {
+            bids: vec![],
+            max: 1,
+        }
+    );
+
+    // Open bids aren't taken.
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let transfer_authority = Keypair::new();
+    let err = helpers::place_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[3].0,
+        &bidders[3].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        1000,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::SealedBidRequired as u32)
+        )
+    );
+
+    // Bids can't be revealed while bidding is open.
+    let err = helpers::reveal_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+        3000,
+        11,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::RevealPeriodNotStarted as u32)
+        )
+    );
+
+    advance_clock(&mut context, 200).await;
+
+    // Bidding is closed.
+    let err = commit_sealed_bid(
+        &mut context,
+        &program_id,
+        &bidders[3],
+        &resource,
+        &mint,
+        5000,
+        seal_bid(&auction_pubkey, &bidders[3].0.pubkey(), 5000, 44),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::SealedBiddingClosed as u32)
+        )
+    );
+
+    // A reveal has to match the commitment.
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let err = helpers::reveal_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &resource,
+        &mint,
+        4500,
+        22,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidReveal as u32)
+        )
+    );
+
+    // The third bidder never reveals.
+    let pre_balance =
+        helpers::get_token_balance(&mut context.banks_client, &bidders[0].0.pubkey()).await;
+    for (bidder, (amount, _, salt)) in bidders.iter().zip(bids.iter()).take(2) {
+        helpers::reveal_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &resource,
+            &mint,
+            *amount,
+            *salt,
+        )
+        .await
+        .expect("reveal_bid");
+    }
+
+    // The part of the deposit above the bid is refunded.
+    let post_balance =
+        helpers::get_token_balance(&mut context.banks_client, &bidders[0].0.pubkey()).await;
+    assert_eq!(post_balance, pre_balance + 2000);
+    let balance =
+        helpers::get_token_balance(&mut context.banks_client, &bidders[0].1.pubkey()).await;
+    assert_eq!(balance, 3000);
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction.bid_state,
+        BidState::SealedBidAuction {
+            bids: vec![
+                Bid(bidders[0].0.pubkey(), 3000),
+                Bid(bidders[1].0.pubkey(), 4000)
+            ],
+            max: 1,
+        }
+    );
+    assert_eq!(auction.is_winner(&bidders[1].0.pubkey()), Some(0));
+
+    // Revealed bids are binding while they are winning.
+    let err = helpers::cancel_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidState as u32)
+        )
+    );
+
+    advance_clock(&mut context, 1000).await;
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+
+    // The winner pays their bid.
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &context.payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    helpers::claim_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &context.payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap();
+    let balance = helpers::get_token_balance(&mut context.banks_client, &collection.pubkey()).await;
+    assert_eq!(balance, 4000);
+
+    // The losing bid and the unrevealed one are refunded.
+    for bidder in [&bidders[0], &bidders[2]].iter() {
+        helpers::cancel_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &resource,
+            &mint,
+        )
+        .await
+        .expect("cancel_bid");
+        let balance =
+            helpers::get_token_balance(&mut context.banks_client, &bidder.1.pubkey()).await;
+        assert_eq!(balance, 0);
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_sealed_bid_rejects_copied_commitment() {
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_sealed_bid_auction(UnrevealedBids::Refund).await;
+
+    // The second bidder copies a commitment made for the first one.
+    commit_sealed_bid(
+        &mut context,
+        &program_id,
+        &bidders[1],
+        &resource,
+        &mint,
+        5000,
+        seal_bid(&auction_pubkey, &bidders[0].0.pubkey(), 3000, 11),
+    )
+    .await
+    .expect("place_sealed_bid");
+
+    advance_clock(&mut context, 200).await;
+
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let err = helpers::reveal_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &resource,
+        &mint,
+        3000,
+        11,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidReveal as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_sealed_bid_auction_forfeits_unrevealed_bids() {
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_sealed_bid_auction(UnrevealedBids::Forfeit).await;
+
+    commit_sealed_bid(
+        &mut context,
+        &program_id,
+        &bidders[0],
+        &resource,
+        &mint,
+        5000,
+        seal_bid(&auction_pubkey, &bidders[0].0.pubkey(), 3000, 11),
+    )
+    .await
+    .expect("place_sealed_bid");
+
+    // Unrevealed bids can be cancelled until bidding closes.
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    helpers::cancel_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("cancel_bid");
+
+    commit_sealed_bid(
+        &mut context,
+        &program_id,
+        &bidders[0],
+        &resource,
+        &mint,
+        5000,
+        seal_bid(&auction_pubkey, &bidders[0].0.pubkey(), 3000, 12),
+    )
+    .await
+    .expect("place_sealed_bid");
+
+    advance_clock(&mut context, 200).await;
+
+    // After that the deposit is forfeit unless the bid is revealed.
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let err = helpers::cancel_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::UnrevealedBidForfeited as u32)
+        )
+    );
+
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &context.payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // The deposit can only be claimed once the auction is over.
+    let err = helpers::claim_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidState as u32)
+        )
+    );
+
+    advance_clock(&mut context, 1000).await;
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+
+    helpers::claim_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap();
+    let balance = helpers::get_token_balance(&mut context.banks_client, &collection.pubkey()).await;
+    assert_eq!(balance, 5000);
+
+    let metadata: BidderMetadata = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(
+                Pubkey::find_program_address(
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        auction_pubkey.as_ref(),
+                        bidders[0].0.pubkey().as_ref(),
+                        "metadata".as_bytes(),
+                    ],
+                    &program_id,
+                )
+                .0,
+            )
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(metadata.last_bid, 0);
+    assert!(!metadata.cancelled);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_sealed_bid_auction_needs_reveal_period() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let err = helpers::create_sealed_bid_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        1,
+        100,
+        SealedBidSettings {
+            reveal_period: 0,
+            unrevealed_bids: UnrevealedBids::Refund,
+        },
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidSealedBidSettings as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_second_price_auction() {
+    let program_id = Pubkey::new_unique();
+    let program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let recent_blockhash = context.last_blockhash;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut context.banks_client, &context.payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_second_price_auction(
+        &mut context.banks_client,
+        &program_id,
+        &context.payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        2,
+        PriceFloor::MinimumPrice([1000, 0, 0, 0]),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let bidders = create_bidders(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &program_id,
+        &auction_pubkey,
+        &mint,
+        &mint_manager,
+    )
+    .await;
+
+    helpers::start_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    // The two highest bids win, and both pay the third highest.
+    let bids = [3000, 5000, 2000];
+    for (bidder, amount) in bidders.iter().zip(bids.iter()) {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &context.payer,
+            &transfer_authority.pubkey(),
+            &bidder.0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+
+        helpers::place_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    helpers::end_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    // Winners can only be claimed once the end has passed.
+    advance_clock(&mut context, 1).await;
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.winner_at(0), Some(bidders[1].0.pubkey()));
+    assert_eq!(auction.winner_at(1), Some(bidders[0].0.pubkey()));
+    assert_eq!(auction.payment_amount(0), 2000);
+    assert_eq!(auction.payment_amount(1), 2000);
+
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &context.payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // The part of a bid above the price has to go back to the bidder.
+    let err = helpers::claim_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &context.payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidRefundAccount as u32)
+        )
+    );
+
+    let mut claimed = 0;
+    for (index, amount) in [(1, 5000), (0, 3000)].iter() {
+        let refund = Keypair::new();
+        helpers::create_token_account(
+            &mut context.banks_client,
+            &context.payer,
+            &recent_blockhash,
+            &refund,
+            &mint,
+            &bidders[*index].0.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        helpers::claim_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &context.payer,
+            &bidders[*index].0,
+            &bidders[*index].1,
+            &collection.pubkey(),
+            &resource,
+            &mint,
+            Some(&refund.pubkey()),
+        )
+        .await
+        .expect("claim_bid");
+        claimed += 2000;
+
+        let pot = bidders[*index].1.pubkey();
+        let balance = helpers::get_token_balance(&mut context.banks_client, &pot).await;
+        assert_eq!(balance, 0);
+        let balance = helpers::get_token_balance(&mut context.banks_client, &refund.pubkey()).await;
+        assert_eq!(balance, amount - 2000);
+        let collected = collection.pubkey();
+        let balance = helpers::get_token_balance(&mut context.banks_client, &collected).await;
+        assert_eq!(balance, claimed);
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_second_price_auction_refuses_instant_sale_price() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let err = helpers::create_second_price_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        1,
+        PriceFloor::None([0; 32]),
+        Some(5000),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidSecondPriceSettings as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_bid_book_auction() {
+    let program_id = Pubkey::new_unique();
+    let program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let recent_blockhash = context.last_blockhash;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut context.banks_client, &context.payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        BID_PAGE.as_bytes(),
+        &0u32.to_le_bytes(),
+    ];
+    let (bid_page_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_bid_book_auction(
+        &mut context.banks_client,
+        &program_id,
+        &context.payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        2,
+        PriceFloor::MinimumPrice([1000, 0, 0, 0]),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // The bids live on their pages, so the auction only makes room for its winners.
+    let auction_account = context
+        .banks_client
+        .get_account(auction_pubkey)
+        .await
+        .expect("get_account")
+        .expect("account not found");
+    assert_eq!(
+        auction_account.data.len(),
+        metaplex_auction::processor::BASE_AUCTION_DATA_SIZE
+            + metaplex_auction::processor::BID_LENGTH * 3
+    );
+
+    let bidders = create_bidders(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &program_id,
+        &auction_pubkey,
+        &mint,
+        &mint_manager,
+    )
+    .await;
+
+    helpers::start_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let bids = [3000, 5000, 2000, 4000];
+    for (bidder, amount) in bidders.iter().zip(bids.iter()) {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &context.payer,
+            &transfer_authority.pubkey(),
+            &bidder.0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+
+        helpers::place_bid_on_book(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+            0,
+            None,
+            None,
+            1,
+        )
+        .await
+        .expect("place_bid_on_book");
+    }
+
+    helpers::cancel_bid_on_book(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[3].0,
+        &bidders[3].1,
+        &resource,
+        &mint,
+        0,
+        None,
+        None,
+    )
+    .await
+    .expect("cancel_bid_on_book");
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction.bid_state,
+        BidState::BidBook {
+            winners: vec![],
+            max: 2,
+            len: 3,
+            pages: 1,
+        }
+    );
+    assert_eq!(auction.num_winners(), 2);
+
+    helpers::end_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    // Winners can only be claimed once the end has passed.
+    advance_clock(&mut context, 1).await;
+
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &context.payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // Ranks on a page are unknown until it is settled.
+    let err = helpers::claim_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &context.payer,
+        &bidders[3].0,
+        &bidders[3].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::BidPageNotSettled as u32)
+        )
+    );
+
+    helpers::settle_bid_page(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &resource,
+        0,
+        None,
+    )
+    .await
+    .expect("settle_bid_page");
+
+    let bid_page: BidPage = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(bid_page_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(bid_page.rank_offset, Some(0));
+    assert_eq!(
+        bid_page.bids,
+        vec![
+            Bid(bidders[1].0.pubkey(), 5000),
+            Bid(bidders[0].0.pubkey(), 3000),
+            Bid(bidders[2].0.pubkey(), 2000),
+        ]
+    );
+    // Settling copies the winners onto the auction, where they are found like any others.
+    let auction: AuctionData = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction.bid_state,
+        BidState::BidBook {
+            winners: vec![
+                Bid(bidders[0].0.pubkey(), 3000),
+                Bid(bidders[1].0.pubkey(), 5000),
+            ],
+            max: 2,
+            len: 3,
+            pages: 1,
+        }
+    );
+    assert_eq!(auction.winner_at(0), Some(bidders[1].0.pubkey()));
+    for (index, rank) in [(1, Some(0)), (0, Some(1)), (2, None)].iter() {
+        let key = bidders[*index].0.pubkey();
+        assert_eq!(auction.is_winner_on_page(&bid_page, &key), *rank);
+        assert_eq!(auction.is_winner(&key), *rank);
+    }
+
+    // The cheap accessors metaplex uses find them as well.
+    let mut auction_account = context
+        .banks_client
+        .get_account(auction_pubkey)
+        .await
+        .expect("get_account")
+        .expect("account not found");
+    let auction_info = AccountInfo::new(
+        &auction_pubkey,
+        false,
+        false,
+        &mut auction_account.lamports,
+        &mut auction_account.data,
+        &program_id,
+        false,
+        0,
+    );
+    assert!(AuctionData::get_winners_settled(&auction_info));
+    assert_eq!(AuctionData::get_num_winners(&auction_info), 2);
+    assert_eq!(
+        AuctionData::get_winner_at(&auction_info, 0),
+        Some(bidders[1].0.pubkey())
+    );
+    assert_eq!(
+        AuctionData::get_winner_bid_amount_at(&auction_info, 1),
+        Some(3000)
+    );
+    for (index, rank) in [(1, Some(0)), (0, Some(1)), (2, None)].iter() {
+        let key = bidders[*index].0.pubkey();
+        assert_eq!(AuctionData::get_is_winner(&auction_info, &key), *rank);
+    }
+
+    // Settling again would copy the winners twice.
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let err = helpers::settle_bid_page(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &resource,
+        0,
+        None,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::BidPageAlreadySettled as u32)
+        )
+    );
+
+    let mut claimed = 0;
+    for (index, amount) in [(1, 5000), (0, 3000)].iter() {
+        helpers::claim_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &context.payer,
+            &bidders[*index].0,
+            &bidders[*index].1,
+            &collection.pubkey(),
+            &resource,
+            &mint,
+            None,
+        )
+        .await
+        .expect("claim_bid");
+        claimed += amount;
+
+        let collected = collection.pubkey();
+        let balance = helpers::get_token_balance(&mut context.banks_client, &collected).await;
+        assert_eq!(balance, claimed);
+    }
+
+    // The third bid lost.
+    let err = helpers::claim_bid(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &context.payer,
+        &bidders[2].0,
+        &bidders[2].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+        None,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidState as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_bid_book_auction_bids_after_page_0_empties() {
+    let program_id = Pubkey::new_unique();
+    let program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let recent_blockhash = context.last_blockhash;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut context.banks_client, &context.payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    let bid_page_pubkeys: Vec<Pubkey> = (0u32..2)
+        .map(|index| {
+            let seeds = &[
+                PREFIX.as_bytes(),
+                &program_id.as_ref(),
+                auction_pubkey.as_ref(),
+                BID_PAGE.as_bytes(),
+                &index.to_le_bytes(),
+            ];
+            Pubkey::find_program_address(seeds, &program_id).0
+        })
+        .collect();
+
+    helpers::create_bid_book_auction(
+        &mut context.banks_client,
+        &program_id,
+        &context.payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        10,
+        PriceFloor::MinimumPrice([1000, 0, 0, 0]),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // One more bidder than fits on a page, so that page 0 splits.
+    let mut bidders = vec![];
+    while bidders.len() <= metaplex_auction::processor::BID_PAGE_CAPACITY {
+        // Enough transactions go by for the blockhash to expire, so keep taking a new one.
+        let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+        bidders.extend(
+            create_bidders(
+                &mut context.banks_client,
+                &context.payer,
+                &recent_blockhash,
+                &program_id,
+                &auction_pubkey,
+                &mint,
+                &mint_manager,
+            )
+            .await,
+        );
+    }
+    bidders.truncate(metaplex_auction::processor::BID_PAGE_CAPACITY + 1);
+
+    helpers::start_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    // Bid from the highest down, so that every bid goes after the ones before it on page 0.
+    for (n, bidder) in bidders.iter().enumerate() {
+        let amount = 10_000 - n as u64 * 10;
+        let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &context.payer,
+            &transfer_authority.pubkey(),
+            &bidder.0,
+            amount,
+        )
+        .await
+        .expect("approve");
+
+        helpers::place_bid_on_book(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            amount,
+            0,
+            None,
+            None,
+            1,
+        )
+        .await
+        .expect("place_bid_on_book");
+    }
+
+    let bid_page: BidPage = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(bid_page_pubkeys[0])
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(bid_page.next, Some(1));
+    let upper = bid_page.bids.len();
+
+    // Cancel every bid left on page 0, page 0 stays at the head of the list while empty.
+    for bidder in bidders[..upper].iter() {
+        let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+        helpers::cancel_bid_on_book(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &resource,
+            &mint,
+            0,
+            None,
+            Some(1),
+        )
+        .await
+        .expect("cancel_bid_on_book");
+    }
+
+    // A bid below the highest bid on page 1 goes on page 1, after the empty page 0.
+    let amount = 10_000 - upper as u64 * 10 - 5;
+    let recent_blockhash = context.banks_client.get_recent_blockhash().await.unwrap();
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &context.payer,
+        &transfer_authority.pubkey(),
+        &bidders[0].0,
+        amount,
+    )
+    .await
+    .expect("approve");
+
+    helpers::place_bid_on_book(
+        &mut context.banks_client,
+        &recent_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        amount,
+        1,
+        Some(0),
+        None,
+        2,
+    )
+    .await
+    .expect("place_bid_on_book");
+
+    let bid_page: BidPage = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(bid_page_pubkeys[1])
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(bid_page.bids.len(), bidders.len() - upper + 1);
+    assert_eq!(
+        bid_page.bids[1],
+        Bid(bidders[0].0.pubkey(), 10_000 - upper as u64 * 10 - 5)
+    );
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction.bid_state,
+        BidState::BidBook {
+            winners: vec![],
+            max: 10,
+            len: bidders.len() - upper + 1,
+            pages: 2,
+        }
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_bid_book_auction_refuses_instant_sale_price() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let err = helpers::create_bid_book_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        1,
+        PriceFloor::None([0; 32]),
+        Some(5000),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidBidBookSettings as u32)
+        )
+    );
+}
+
+/// Bidding is open for 100 seconds after the start, and the authority has 50 seconds after that to
+/// reveal a floor of 2500. The first bidder bids 3000 and the second 2000, for two lots.
+async fn setup_blinded_floor_auction(
+    unrevealed_floor: UnrevealedFloor,
+) -> (
+    Pubkey,
+    ProgramTestContext,
+    Vec<(Keypair, Keypair, Pubkey)>,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+) {
+    let program_id = Pubkey::new_unique();
+    let program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+    let recent_blockhash = context.last_blockhash;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut context.banks_client, &context.payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_blinded_floor_auction(
+        &mut context.banks_client,
+        &program_id,
+        &context.payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        2,
+        100,
+        seal(2500, 7),
+        BlindedFloorSettings {
+            reveal_deadline: 50,
+            unrevealed_floor,
+        },
+    )
+    .await
+    .unwrap();
+
+    let bidders = create_bidders(
+        &mut context.banks_client,
+        &context.payer,
+        &recent_blockhash,
+        &program_id,
+        &auction_pubkey,
+        &mint,
+        &mint_manager,
+    )
+    .await;
+
+    helpers::start_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    for (bidder, amount) in bidders.iter().zip([3000, 2000].iter()) {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &context.payer,
+            &transfer_authority.pubkey(),
+            &bidder.0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+
+        helpers::place_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    (program_id, context, bidders, resource, mint, auction_pubkey)
+}
+
+/// Fund a fresh account to end auctions with, anyone can.
+async fn create_cranker(context: &mut ProgramTestContext) -> Keypair {
+    let cranker = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &cranker.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+    cranker
+}
+
+async fn get_auction(context: &mut ProgramTestContext, auction_pubkey: &Pubkey) -> AuctionData {
+    try_from_slice_unchecked(
+        &context
+            .banks_client
+            .get_account(*auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap()
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_crank_end_auction_without_floor() {
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_blinded_floor_auction(UnrevealedFloor::NoFloor).await;
+    let recent_blockhash = context.last_blockhash;
+
+    // Nobody can end the auction while bids can still come in, or while the authority can still
+    // reveal the floor.
+    for seconds in [0, 101].iter() {
+        advance_clock(&mut context, *seconds).await;
+        let cranker = create_cranker(&mut context).await;
+        let err = helpers::crank_end_auction(
+            &mut context.banks_client,
+            &program_id,
+            &recent_blockhash,
+            &cranker,
+            &resource,
+        )
+        .await
+        .unwrap_err()
+        .unwrap();
+        let error = if *seconds == 0 {
+            AuctionError::AuctionNotOver
+        } else {
+            AuctionError::RevealDeadlineNotPassed
+        };
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+        );
+    }
+
+    // Once the deadline passes the floor can't be revealed anymore.
+    advance_clock(&mut context, 50).await;
+    let err = helpers::end_auction_with_reveal(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+        (2500, 7),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::RevealDeadlinePassed as u32)
+        )
+    );
+
+    let cranker = create_cranker(&mut context).await;
+    helpers::crank_end_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &cranker,
+        &resource,
+    )
+    .await
+    .expect("crank_end_auction");
+
+    // Both bids win, including the one below the floor that was never revealed.
+    let auction = get_auction(&mut context, &auction_pubkey).await;
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(auction.price_floor, PriceFloor::None([0; 32]));
+    assert_eq!(auction.is_winner(&bidders[0].0.pubkey()), Some(0));
+    assert_eq!(auction.is_winner(&bidders[1].0.pubkey()), Some(1));
+
+    // It only ends once.
+    let cranker = create_cranker(&mut context).await;
+    let err = helpers::crank_end_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &cranker,
+        &resource,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::AuctionTransitionInvalid as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_crank_end_auction_refunds_bids() {
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_blinded_floor_auction(UnrevealedFloor::RefundBids).await;
+    let recent_blockhash = context.last_blockhash;
+
+    advance_clock(&mut context, 151).await;
+    let cranker = create_cranker(&mut context).await;
+    helpers::crank_end_auction(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &cranker,
+        &resource,
+    )
+    .await
+    .expect("crank_end_auction");
+
+    let auction = get_auction(&mut context, &auction_pubkey).await;
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(auction.num_winners(), 0);
+
+    // Nobody won, so every bid can be taken back in full.
+    for (bidder, amount) in bidders.iter().zip([3000, 2000].iter()) {
+        assert_eq!(auction.is_winner(&bidder.0.pubkey()), None);
+
+        let balance =
+            helpers::get_token_balance(&mut context.banks_client, &bidder.0.pubkey()).await;
+        helpers::cancel_bid(
+            &mut context.banks_client,
+            &recent_blockhash,
+            &program_id,
+            &context.payer,
+            &bidder.0,
+            &bidder.1,
+            &resource,
+            &mint,
+        )
+        .await
+        .expect("cancel_bid");
+
+        let refunded =
+            helpers::get_token_balance(&mut context.banks_client, &bidder.0.pubkey()).await;
+        assert_eq!(refunded, balance + amount);
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_end_auction_reveals_floor_before_deadline() {
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_blinded_floor_auction(UnrevealedFloor::NoFloor).await;
+    let recent_blockhash = context.last_blockhash;
+
+    advance_clock(&mut context, 101).await;
+    helpers::end_auction_with_reveal(
+        &mut context.banks_client,
+        &program_id,
+        &recent_blockhash,
+        &context.payer,
+        &resource,
+        (2500, 7),
+    )
+    .await
+    .expect("end_auction_with_reveal");
+
+    // Only the bid above the revealed floor wins.
+    let auction = get_auction(&mut context, &auction_pubkey).await;
+    assert_eq!(auction.state, AuctionState::Ended);
+    let floor = PriceFloor::MinimumPrice([2500, 0, 0, 0]);
+    assert_eq!(auction.price_floor, floor);
+    assert_eq!(auction.is_winner(&bidders[0].0.pubkey()), Some(0));
+    assert_eq!(auction.is_winner(&bidders[1].0.pubkey()), None);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_blinded_floor_settings_need_a_deadline() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let err = helpers::create_blinded_floor_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        1,
+        100,
+        seal(2500, 7),
+        BlindedFloorSettings {
+            reveal_deadline: 0,
+            unrevealed_floor: UnrevealedFloor::NoFloor,
+        },
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidBlindedFloorSettings as u32)
+        )
+    );
+}
@@ -470,6 +470,10 @@ pub enum MetaplexError {
     /// Auction manager already in finished state
     #[error("Auction manager already in finished state")]
     AuctionManagerInFishedState,
+
+    /// Winners of a bid book auction aren't known until its pages are settled
+    #[error("Winners of a bid book auction aren't known until its pages are settled")]
+    BidBookNotSettled,
 }
 
 impl PrintProgramError for MetaplexError {
@@ -248,6 +248,8 @@ pub enum MetaplexInstruction {
     ///   4. `[]` Store key
     ///   5. `[]` Auction program
     ///   6. `[]` Clock sysvar
+    ///   7. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended']),
+    ///                   optional but required for dutch auctions
     StartAuction,
 
     /// If the auction manager is in a Disbursing or Finished state, then this means Auction must be in Ended state.
@@ -273,6 +275,10 @@ pub enum MetaplexInstruction {
     ///   10. `[]` Clock sysvar
     ///   11. `[]` Token program
     ///   12. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   13. `[]` Optional bidder metadata (pda of ['auction', auction program id, auction key, bidder key, 'metadata']),
+    ///                   needed to claim the deposit of a sealed bid that was never revealed
+    ///   14. `[writable]` Optional bidder token account, needed to refund the part of a second price auction bid
+    ///                   above the price it settled at
     ClaimBid,
 
     /// At any time, the auction manager authority may empty whatever funds are in the accept payment account
@@ -1056,17 +1062,24 @@ pub fn create_start_auction_instruction(
     auction: Pubkey,
     auction_manager_authority: Pubkey,
     store: Pubkey,
+    auction_extended: Option<Pubkey>,
 ) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(auction_manager, false),
+        AccountMeta::new(auction, false),
+        AccountMeta::new_readonly(auction_manager_authority, true),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(metaplex_auction::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    if let Some(auction_extended) = auction_extended {
+        accounts.push(AccountMeta::new(auction_extended, false));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(auction_manager, false),
-            AccountMeta::new(auction, false),
-            AccountMeta::new_readonly(auction_manager_authority, true),
-            AccountMeta::new_readonly(store, false),
-            AccountMeta::new_readonly(metaplex_auction::id(), false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-        ],
+        accounts,
         data: MetaplexInstruction::StartAuction.try_to_vec().unwrap(),
     }
 }
@@ -31,6 +31,8 @@ pub fn issue_claim_bid<'a>(
     token_mint: AccountInfo<'a>,
     clock: AccountInfo<'a>,
     token_program: AccountInfo<'a>,
+    bidder_meta: Option<AccountInfo<'a>>,
+    bidder_token: Option<AccountInfo<'a>>,
     vault: Pubkey,
     signer_seeds: &[&[u8]],
 ) -> ProgramResult {
@@ -52,20 +54,38 @@ pub fn issue_claim_bid<'a>(
         auction_extended_key = Some(*auction_extended_account.key);
         account_infos.push(auction_extended_account);
     }
-    invoke_signed(
-        &claim_bid_instruction(
-            *auction_program.key,
-            *accept_payment.key,
-            *authority.key,
-            *bidder.key,
-            *bidder_pot_token_acct.key,
-            *token_mint.key,
-            auction_extended_key,
-            ClaimBidArgs { resource: vault },
-        ),
-        account_infos.as_ref(),
-        &[&signer_seeds],
-    )?;
+    // The bidder token account can only follow the bidder metadata.
+    let bidder_token = bidder_meta.as_ref().and(bidder_token);
+    let mut instruction = claim_bid_instruction(
+        *auction_program.key,
+        *accept_payment.key,
+        *authority.key,
+        *bidder.key,
+        *bidder_pot_token_acct.key,
+        *token_mint.key,
+        auction_extended_key,
+        bidder_token.as_ref().map(|bidder_token| *bidder_token.key),
+        ClaimBidArgs { resource: vault },
+    );
+
+    // The bidder metadata follows the auction extended, and is only needed to claim the deposit
+    // of an unrevealed sealed bid. The bidder token account after it gets the refund of a second
+    // price auction bid.
+    if auction_extended_key.is_some() {
+        match bidder_meta {
+            Some(bidder_meta) => {
+                account_infos.push(bidder_meta);
+                if let Some(bidder_token) = bidder_token {
+                    account_infos.push(bidder_token);
+                }
+            }
+            None => {
+                instruction.accounts.pop();
+            }
+        }
+    }
+
+    invoke_signed(&instruction, account_infos.as_ref(), &[&signer_seeds])?;
 
     Ok(())
 }
@@ -85,6 +105,8 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
     let clock_info = next_account_info(account_info_iter)?;
     let token_program_info = next_account_info(account_info_iter)?;
     let auction_extended_info = next_account_info(account_info_iter).ok();
+    let bidder_meta_info = next_account_info(account_info_iter).ok();
+    let bidder_token_info = next_account_info(account_info_iter).ok();
 
     let mut auction_manager = get_auction_manager(auction_manager_info)?;
     let store = Store::from_account_info(store_info)?;
@@ -102,6 +124,12 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
     if let Some(auction_extended) = auction_extended_info {
         assert_owned_by(auction_extended, &store.auction_program)?;
     }
+    if let Some(bidder_meta) = bidder_meta_info {
+        assert_owned_by(bidder_meta, &store.auction_program)?;
+    }
+    if let Some(bidder_token) = bidder_token_info {
+        assert_owned_by(bidder_token, &spl_token::id())?;
+    }
 
     if auction_manager.store() != *store_info.key {
         return Err(MetaplexError::AuctionManagerStoreMismatch.into());
@@ -132,7 +160,9 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
         instant_sale_price =
             AuctionDataExtended::get_instant_sale_price(&auction_extended.data.borrow());
     }
-    if !instant_sale_price.is_some() {
+    // Dutch auction winners all paid the price the auction was at, so just like instant sale
+    // winners they don't need to wait for it to end.
+    if !instant_sale_price.is_some() && !AuctionData::is_dutch_auction(auction_info) {
         if auction.state != AuctionState::Ended {
             return Err(MetaplexError::AuctionHasNotEnded.into());
         }
@@ -170,6 +200,8 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
         token_mint_info.clone(),
         clock_info.clone(),
         token_program_info.clone(),
+        bidder_meta_info.cloned(),
+        bidder_token_info.cloned(),
         *vault_info.key,
         authority_seeds,
     )?;
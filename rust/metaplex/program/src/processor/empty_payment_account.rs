@@ -92,7 +92,7 @@ fn calculate_owed_amount(
     )?;
 
     let mut amount_available_to_split: u128 = match winning_config_index {
-        Some(index) => auction.bid_state.amount(*index as usize) as u128,
+        Some(index) => auction.payment_amount(*index as usize) as u128,
         None => {
             // this means the amount owed is the amount collected from participation nft bids.
             auction_manager.get_collected_to_accept_payment(safety_deposit_config_info)?
@@ -30,6 +30,7 @@ pub fn issue_end_auction<'a>(
         &end_auction_instruction(
             *auction_program.key,
             *authority.key,
+            None,
             EndAuctionArgs {
                 resource: vault,
                 reveal,
@@ -103,7 +104,11 @@ pub fn process_end_auction(
 
     if auction_data_extended.instant_sale_price.is_some() {
         match auction.bid_state {
-            BidState::EnglishAuction { .. } => {
+            BidState::EnglishAuction { .. }
+            | BidState::DutchAuction { .. }
+            | BidState::SealedBidAuction { .. }
+            | BidState::SecondPriceAuction { .. }
+            | BidState::BidBook { .. } => {
                 auction_manager.set_status(AuctionManagerStatus::Disbursing);
             }
             BidState::OpenEdition { .. } => {
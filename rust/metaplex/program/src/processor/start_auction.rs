@@ -21,18 +21,26 @@ pub fn issue_start_auction<'a>(
     authority: AccountInfo<'a>,
     auction: AccountInfo<'a>,
     clock: AccountInfo<'a>,
+    auction_extended: Option<AccountInfo<'a>>,
     vault: Pubkey,
     signer_seeds: &[&[u8]],
 ) -> ProgramResult {
-    invoke_signed(
-        &start_auction_instruction(
-            *auction_program.key,
-            *authority.key,
-            StartAuctionArgs { resource: vault },
-        ),
-        &[auction_program, authority, auction, clock],
-        &[&signer_seeds],
-    )?;
+    let mut instruction = start_auction_instruction(
+        *auction_program.key,
+        *authority.key,
+        StartAuctionArgs { resource: vault },
+    );
+    let mut account_infos = vec![auction_program, authority, auction, clock];
+
+    match auction_extended {
+        Some(auction_extended) => account_infos.push(auction_extended),
+        // Only dutch auctions need it to start
+        None => {
+            instruction.accounts.pop();
+        }
+    }
+
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds])?;
 
     Ok(())
 }
@@ -45,6 +53,7 @@ pub fn process_start_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     let store_info = next_account_info(account_info_iter)?;
     let auction_program_info = next_account_info(account_info_iter)?;
     let clock_info = next_account_info(account_info_iter)?;
+    let auction_extended_info = next_account_info(account_info_iter).ok();
 
     let mut auction_manager = get_auction_manager(auction_manager_info)?;
     let auction = AuctionData::from_account_info(auction_info)?;
@@ -85,6 +94,7 @@ pub fn process_start_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
         auction_manager_info.clone(),
         auction_info.clone(),
         clock_info.clone(),
+        auction_extended_info.cloned(),
         auction_manager.vault(),
         authority_seeds,
     )?;
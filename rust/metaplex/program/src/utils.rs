@@ -139,19 +139,22 @@ pub fn assert_at_least_one_creator_matches_or_store_public_and_all_verified(
 
         let mut found = false;
         for creator in creators {
-            // Now find at least one creator that can make this pda in the list
-            let (key, _) = Pubkey::find_program_address(
-                &[
-                    PREFIX.as_bytes(),
-                    program_id.as_ref(),
-                    auction_manager.store().as_ref(),
-                    creator.address.as_ref(),
-                ],
-                program_id,
-            );
-
-            if key == *whitelisted_creator_info.key {
-                found = true;
+            // Now find at least one creator that can make this pda in the list. Deriving
+            // is expensive, so stop once found, every creator still has to be verified.
+            if !found {
+                let (key, _) = Pubkey::find_program_address(
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        auction_manager.store().as_ref(),
+                        creator.address.as_ref(),
+                    ],
+                    program_id,
+                );
+
+                if key == *whitelisted_creator_info.key {
+                    found = true;
+                }
             }
 
             if !creator.verified {
@@ -189,6 +192,11 @@ pub fn assert_auction_is_ended_or_valid_instant_sale(
         return Ok(());
     }
 
+    // Every dutch auction winner bought at the current price, like an instant sale.
+    if AuctionData::is_dutch_auction(auction_info) && win_index.is_some() {
+        return Ok(());
+    }
+
     let instant_sale_price = auction_extended_info
         .and_then(|info| AuctionDataExtended::get_instant_sale_price(&info.data.borrow()));
 
@@ -428,6 +436,11 @@ fn calculate_win_index(
     user_provided_win_index: Option<Option<usize>>,
     overwrite_win_index: Option<usize>,
 ) -> Result<Option<usize>, ProgramError> {
+    // A bid book only knows its winners once every page holding them has been settled.
+    if !AuctionData::get_winners_settled(auction_info) {
+        return Err(MetaplexError::BidBookNotSettled.into());
+    }
+
     let mut win_index: Option<usize>;
     // User provided us with an option of an option telling us what if anything they won. We need to validate.
     if let Some(up_win_index) = user_provided_win_index {
@@ -932,6 +945,7 @@ pub fn end_auction<'a: 'b, 'b>(
         &end_auction_instruction(
             *auction_program.key,
             *authority.key,
+            None,
             EndAuctionArgs {
                 resource,
                 reveal: None,
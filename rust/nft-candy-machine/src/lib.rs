@@ -11,7 +11,8 @@ use {
     metaplex_token_metadata::{
         instruction::{create_master_edition, create_metadata_accounts, update_metadata_accounts},
         state::{
-            MAX_CREATOR_LEN, MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+            MAX_CREATOR_LEN, MAX_FIXED_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
+            MAX_URI_LENGTH,
         },
     },
     spl_token::state::Mint,
@@ -251,8 +252,9 @@ pub mod nft_candy_machine {
             config.data.symbol.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
         config.data.symbol = new_symbol;
 
-        // - 1 because we are going to be a creator
-        if config.data.creators.len() > MAX_CREATOR_LIMIT - 1 {
+        // - 1 because we are going to be a creator. The config account reserves a fixed
+        // number of creator slots, so it stays on the fixed limit until its layout changes.
+        if config.data.creators.len() > MAX_FIXED_CREATOR_LIMIT - 1 {
             return Err(ErrorCode::TooManyCreators.into());
         }
 
@@ -534,7 +536,7 @@ pub const CONFIG_ARRAY_START: usize = 32 + // authority
 4 + 6 + // uuid + u32 len
 4 + MAX_SYMBOL_LENGTH + // u32 len + symbol
 2 + // seller fee basis points
-1 + 4 + MAX_CREATOR_LIMIT*MAX_CREATOR_LEN + // optional + u32 len + actual vec
+1 + 4 + MAX_FIXED_CREATOR_LIMIT*MAX_CREATOR_LEN + // optional + u32 len + actual vec
 8 + //max supply
 1 + // is mutable
 1 + // retain authority
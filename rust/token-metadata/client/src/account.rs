@@ -0,0 +1,150 @@
+//! Decoding of raw Token Metadata account data
+
+use {
+    crate::error::DecodeError,
+    borsh::BorshDeserialize,
+    metaplex_token_metadata::state::{
+        Edition, EditionMarker, EditionReservation, Key, LegacyPrintingRecord, MasterEditionV1,
+        MasterEditionV2, Metadata, PendingUpdateAuthority, PrintDelegateRecord, ReservationListV1,
+        ReservationListV2, SaleAuthorityRecord, UseAuthorityRecord,
+    },
+    solana_program::borsh::try_from_slice_unchecked,
+};
+
+/// Any account owned by the Token Metadata program, as told apart by its leading Key.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum TokenMetadataAccount {
+    Metadata(Metadata),
+    MasterEditionV1(MasterEditionV1),
+    MasterEditionV2(MasterEditionV2),
+    Edition(Edition),
+    EditionMarker(EditionMarker),
+    ReservationListV1(ReservationListV1),
+    ReservationListV2(ReservationListV2),
+    PendingUpdateAuthority(PendingUpdateAuthority),
+    UseAuthorityRecord(UseAuthorityRecord),
+    SaleAuthorityRecord(SaleAuthorityRecord),
+    PrintDelegateRecord(PrintDelegateRecord),
+    EditionReservation(EditionReservation),
+    LegacyPrintingRecord(LegacyPrintingRecord),
+}
+
+impl TokenMetadataAccount {
+    pub fn key(&self) -> Key {
+        match self {
+            TokenMetadataAccount::Metadata(_) => Key::MetadataV1,
+            TokenMetadataAccount::MasterEditionV1(_) => Key::MasterEditionV1,
+            TokenMetadataAccount::MasterEditionV2(_) => Key::MasterEditionV2,
+            TokenMetadataAccount::Edition(_) => Key::EditionV1,
+            TokenMetadataAccount::EditionMarker(_) => Key::EditionMarker,
+            TokenMetadataAccount::ReservationListV1(_) => Key::ReservationListV1,
+            TokenMetadataAccount::ReservationListV2(_) => Key::ReservationListV2,
+            TokenMetadataAccount::PendingUpdateAuthority(_) => Key::PendingUpdateAuthority,
+            TokenMetadataAccount::UseAuthorityRecord(_) => Key::UseAuthorityRecord,
+            TokenMetadataAccount::SaleAuthorityRecord(_) => Key::SaleAuthorityRecord,
+            TokenMetadataAccount::PrintDelegateRecord(_) => Key::PrintDelegateRecord,
+            TokenMetadataAccount::EditionReservation(_) => Key::EditionReservation,
+            TokenMetadataAccount::LegacyPrintingRecord(_) => Key::LegacyPrintingRecord,
+        }
+    }
+}
+
+/// Read the Key an account's data starts with.
+pub fn account_key(data: &[u8]) -> Result<Key, DecodeError> {
+    let first = *data.first().ok_or(DecodeError::EmptyAccount)?;
+    match Key::try_from_slice(&[first]) {
+        Ok(Key::Uninitialized) => Err(DecodeError::Uninitialized),
+        Ok(key) => Ok(key),
+        Err(_) => Err(DecodeError::UnknownKey(first)),
+    }
+}
+
+/// Decode the data of any account owned by the Token Metadata program. Strings of metadata
+/// accounts that were puffed out to their maximum length come back without the null padding.
+pub fn decode_account(data: &[u8]) -> Result<TokenMetadataAccount, DecodeError> {
+    // Trailing zeroes are padding on fixed size accounts, so don't insist on reading all of the data.
+    Ok(match account_key(data)? {
+        Key::Uninitialized => return Err(DecodeError::Uninitialized),
+        Key::MetadataV1 => TokenMetadataAccount::Metadata(decode_metadata(data)?),
+        Key::MasterEditionV1 => {
+            TokenMetadataAccount::MasterEditionV1(try_from_slice_unchecked(data)?)
+        }
+        Key::MasterEditionV2 => {
+            TokenMetadataAccount::MasterEditionV2(try_from_slice_unchecked(data)?)
+        }
+        Key::EditionV1 => TokenMetadataAccount::Edition(try_from_slice_unchecked(data)?),
+        Key::EditionMarker => TokenMetadataAccount::EditionMarker(try_from_slice_unchecked(data)?),
+        Key::ReservationListV1 => {
+            TokenMetadataAccount::ReservationListV1(try_from_slice_unchecked(data)?)
+        }
+        Key::ReservationListV2 => {
+            TokenMetadataAccount::ReservationListV2(try_from_slice_unchecked(data)?)
+        }
+        Key::PendingUpdateAuthority => {
+            TokenMetadataAccount::PendingUpdateAuthority(try_from_slice_unchecked(data)?)
+        }
+        Key::UseAuthorityRecord => {
+            TokenMetadataAccount::UseAuthorityRecord(try_from_slice_unchecked(data)?)
+        }
+        Key::SaleAuthorityRecord => {
+            TokenMetadataAccount::SaleAuthorityRecord(try_from_slice_unchecked(data)?)
+        }
+        Key::PrintDelegateRecord => {
+            TokenMetadataAccount::PrintDelegateRecord(try_from_slice_unchecked(data)?)
+        }
+        Key::EditionReservation => {
+            TokenMetadataAccount::EditionReservation(try_from_slice_unchecked(data)?)
+        }
+        Key::LegacyPrintingRecord => {
+            TokenMetadataAccount::LegacyPrintingRecord(try_from_slice_unchecked(data)?)
+        }
+    })
+}
+
+/// Decode a Metadata account, trimming the null padding puffed accounts carry in their strings.
+pub fn decode_metadata(data: &[u8]) -> Result<Metadata, DecodeError> {
+    assert_key(data, Key::MetadataV1)?;
+    let mut metadata: Metadata = try_from_slice_unchecked(data)?;
+    metadata.data.name = trim_padding(&metadata.data.name);
+    metadata.data.symbol = trim_padding(&metadata.data.symbol);
+    metadata.data.uri = trim_padding(&metadata.data.uri);
+
+    Ok(metadata)
+}
+
+/// Decode a Master Edition account, which may be either a V1 or a V2.
+pub fn decode_master_edition(data: &[u8]) -> Result<TokenMetadataAccount, DecodeError> {
+    match account_key(data)? {
+        Key::MasterEditionV1 | Key::MasterEditionV2 => decode_account(data),
+        found => Err(DecodeError::KeyMismatch {
+            expected: Key::MasterEditionV2,
+            found,
+        }),
+    }
+}
+
+/// Decode a print's Edition account.
+pub fn decode_edition(data: &[u8]) -> Result<Edition, DecodeError> {
+    assert_key(data, Key::EditionV1)?;
+    Ok(try_from_slice_unchecked(data)?)
+}
+
+/// Decode an Edition Marker account.
+pub fn decode_edition_marker(data: &[u8]) -> Result<EditionMarker, DecodeError> {
+    assert_key(data, Key::EditionMarker)?;
+    Ok(try_from_slice_unchecked(data)?)
+}
+
+fn assert_key(data: &[u8], expected: Key) -> Result<(), DecodeError> {
+    let found = account_key(data)?;
+    if found != expected {
+        return Err(DecodeError::KeyMismatch { expected, found });
+    }
+
+    Ok(())
+}
+
+fn trim_padding(value: &str) -> String {
+    value.trim_end_matches(char::from(0)).to_string()
+}
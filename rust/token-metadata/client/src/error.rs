@@ -0,0 +1,27 @@
+//! Error types
+
+use {metaplex_token_metadata::state::Key, thiserror::Error};
+
+/// Errors that may be returned when decoding Token Metadata accounts.
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    /// Account has no data
+    #[error("Account has no data")]
+    EmptyAccount,
+
+    /// Account is not initialized
+    #[error("Account is not initialized")]
+    Uninitialized,
+
+    /// Account starts with a key this program does not write
+    #[error("Unknown account key {0}")]
+    UnknownKey(u8),
+
+    /// Account is of a different type than the one asked for
+    #[error("Expected a {expected:?} account but found a {found:?}")]
+    KeyMismatch { expected: Key, found: Key },
+
+    /// Account data does not deserialize into its type
+    #[error("Invalid account data: {0}")]
+    InvalidData(#[from] std::io::Error),
+}
@@ -0,0 +1,10 @@
+//! Off-chain helpers for the Token Metadata program: decoding raw account data,
+//! deriving account addresses and building instructions.
+
+pub mod account;
+pub mod error;
+pub mod pda;
+
+// Instruction builders and account types come straight from the program
+pub use metaplex_token_metadata::{deprecated_instruction, id, instruction, state, ID};
+pub use solana_program;
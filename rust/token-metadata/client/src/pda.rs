@@ -0,0 +1,76 @@
+//! Addresses of the accounts owned by the Token Metadata program
+
+use {
+    metaplex_token_metadata::state::{EDITION, EDITION_MARKER_BIT_SIZE, PREFIX, RESERVATION},
+    solana_program::pubkey::Pubkey,
+};
+
+pub use metaplex_token_metadata::instruction::{
+    find_burner_address, find_edition_reservation_address, find_legacy_printing_record_address,
+    find_pending_update_authority_address, find_print_delegate_record_address,
+    find_sale_authority_record_address, find_transfer_authority_address,
+    find_use_authority_record_address,
+};
+
+/// Derives the metadata pda for a mint
+pub fn find_metadata_address(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), mint.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Derives the pda of a mint's Master Edition or Edition, whichever it has
+pub fn find_edition_address(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Derives the pda of the edition marker that records whether an edition of a master edition's
+/// mint has been printed
+pub fn find_edition_marker_address(
+    program_id: &Pubkey,
+    master_mint: &Pubkey,
+    edition: u64,
+) -> Pubkey {
+    let marker_number = (edition / EDITION_MARKER_BIT_SIZE).to_string();
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION.as_bytes(),
+            marker_number.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Derives the pda of the deprecated reservation list a resource made on a Master Edition V1
+pub fn find_reservation_list_address(
+    program_id: &Pubkey,
+    master_edition: &Pubkey,
+    resource: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_edition.as_ref(),
+            RESERVATION.as_bytes(),
+            resource.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
@@ -0,0 +1,117 @@
+use borsh::BorshSerialize;
+use metaplex_token_metadata_client::{
+    account::{decode_account, decode_edition_marker, decode_metadata, TokenMetadataAccount},
+    error::DecodeError,
+    id, pda,
+    state::{
+        Data, EditionMarker, Key, MasterEditionV2, Metadata, MAX_METADATA_LEN, MAX_NAME_LENGTH,
+        MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+    },
+};
+use solana_program::pubkey::Pubkey;
+
+fn puff(value: &str, len: usize) -> String {
+    value.to_string() + &"\u{0}".repeat(len - value.len())
+}
+
+fn metadata(name: String, symbol: String, uri: String) -> Metadata {
+    Metadata {
+        key: Key::MetadataV1,
+        update_authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        data: Data {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 500,
+            creators: None,
+        },
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: Some(255),
+        collection: None,
+        uses: None,
+        content_hash: None,
+        allowed_transfer_programs: None,
+        token_standard: None,
+    }
+}
+
+#[test]
+fn decode_puffed_metadata() {
+    let puffed = metadata(
+        puff("Test", MAX_NAME_LENGTH),
+        puff("TST", MAX_SYMBOL_LENGTH),
+        puff("uri", MAX_URI_LENGTH),
+    );
+    let mut data = puffed.try_to_vec().unwrap();
+    data.resize(MAX_METADATA_LEN, 0);
+
+    let decoded = decode_metadata(&data).unwrap();
+    assert_eq!(decoded.data.name, "Test");
+    assert_eq!(decoded.data.symbol, "TST");
+    assert_eq!(decoded.data.uri, "uri");
+    assert_eq!(decoded.mint, puffed.mint);
+    assert_eq!(decoded.edition_nonce, Some(255));
+}
+
+#[test]
+fn decode_any_account_by_key() {
+    let master_edition = MasterEditionV2 {
+        key: Key::MasterEditionV2,
+        supply: 3,
+        max_supply: Some(10),
+        reserved: [0; 64],
+        edition_template: None,
+    };
+    match decode_account(&master_edition.try_to_vec().unwrap()).unwrap() {
+        TokenMetadataAccount::MasterEditionV2(decoded) => assert_eq!(decoded, master_edition),
+        other => panic!("Decoded a {:?}", other.key()),
+    }
+
+    let sized_metadata = metadata("Test".to_string(), "TST".to_string(), "uri".to_string());
+    let decoded = decode_account(&sized_metadata.try_to_vec().unwrap()).unwrap();
+    assert_eq!(decoded.key(), Key::MetadataV1);
+}
+
+#[test]
+fn fail_wrong_key() {
+    let marker = EditionMarker {
+        key: Key::EditionMarker,
+        ledger: [0; 31],
+    };
+    let data = marker.try_to_vec().unwrap();
+    assert_eq!(decode_edition_marker(&data).unwrap(), marker);
+
+    match decode_metadata(&data) {
+        Err(DecodeError::KeyMismatch { expected, found }) => {
+            assert_eq!(expected, Key::MetadataV1);
+            assert_eq!(found, Key::EditionMarker);
+        }
+        other => panic!("Unexpected result {:?}", other.map(|_| ())),
+    }
+
+    assert!(matches!(
+        decode_account(&[0; 10]),
+        Err(DecodeError::Uninitialized)
+    ));
+    assert!(matches!(
+        decode_account(&[200]),
+        Err(DecodeError::UnknownKey(200))
+    ));
+}
+
+#[test]
+fn edition_marker_address_covers_its_range() {
+    let master_mint = Pubkey::new_unique();
+    let first = pda::find_edition_marker_address(&id(), &master_mint, 1);
+
+    assert_eq!(
+        first,
+        pda::find_edition_marker_address(&id(), &master_mint, 247)
+    );
+    assert_ne!(
+        first,
+        pda::find_edition_marker_address(&id(), &master_mint, 248)
+    );
+}
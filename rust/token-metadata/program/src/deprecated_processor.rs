@@ -5,8 +5,8 @@ use {
         error::MetadataError,
         state::{
             get_reservation_list, Key, MasterEditionV1, Metadata, Reservation, ReservationListV2,
-            EDITION, MAX_MASTER_EDITION_LEN, MAX_RESERVATIONS, MAX_RESERVATION_LIST_SIZE, PREFIX,
-            RESERVATION,
+            TokenStandard, EDITION, MAX_MASTER_EDITION_LEN, MAX_RESERVATIONS,
+            MAX_RESERVATION_LIST_SIZE, PREFIX, RESERVATION,
         },
         utils::{
             assert_derivation, assert_initialized, assert_mint_authority_matches_mint,
@@ -96,6 +96,12 @@ pub fn process_deprecated_create_master_edition(
         return Err(MetadataError::EditionMintDecimalsShouldBeZero.into());
     }
 
+    if let Some(TokenStandard::Fungible) | Some(TokenStandard::FungibleAsset) =
+        metadata.token_standard
+    {
+        return Err(MetadataError::FungibleCannotBeMasterEdition.into());
+    }
+
     assert_update_authority_is_correct(&metadata, update_authority_info)?;
 
     if mint.supply != 1 {
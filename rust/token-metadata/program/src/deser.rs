@@ -0,0 +1,46 @@
+use {
+    crate::state::{Collection, Data, Key, Metadata, TokenStandard, Uses},
+    borsh::{maybestd::io::Error, BorshDeserialize},
+    solana_program::pubkey::Pubkey,
+};
+
+/// Deserialize a Metadata account written by any version of this program.
+/// Accounts are zero padded past their contents, so fields appended after the data
+/// read as None, as they do when the buffer runs out before them.
+pub fn meta_deser(buf: &mut &[u8]) -> Result<Metadata, Error> {
+    let key: Key = BorshDeserialize::deserialize(buf)?;
+    let update_authority: Pubkey = BorshDeserialize::deserialize(buf)?;
+    let mint: Pubkey = BorshDeserialize::deserialize(buf)?;
+    let data: Data = BorshDeserialize::deserialize(buf)?;
+    let primary_sale_happened: bool = BorshDeserialize::deserialize(buf)?;
+    let is_mutable: bool = BorshDeserialize::deserialize(buf)?;
+    let edition_nonce: Option<u8> = deser_trailing_option(buf)?;
+    let collection: Option<Collection> = deser_trailing_option(buf)?;
+    let uses: Option<Uses> = deser_trailing_option(buf)?;
+    let content_hash: Option<[u8; 32]> = deser_trailing_option(buf)?;
+    let allowed_transfer_programs: Option<Vec<Pubkey>> = deser_trailing_option(buf)?;
+    let token_standard: Option<TokenStandard> = deser_trailing_option(buf)?;
+
+    Ok(Metadata {
+        key,
+        update_authority,
+        mint,
+        data,
+        primary_sale_happened,
+        is_mutable,
+        edition_nonce,
+        collection,
+        uses,
+        content_hash,
+        allowed_transfer_programs,
+        token_standard,
+    })
+}
+
+fn deser_trailing_option<T: BorshDeserialize>(buf: &mut &[u8]) -> Result<Option<T>, Error> {
+    if buf.is_empty() {
+        return Ok(None);
+    }
+
+    BorshDeserialize::deserialize(buf)
+}
@@ -306,6 +306,170 @@ pub enum MetadataError {
     // In the legacy system the reservation needs to be of size one for cpu limit reasons
     #[error("In the legacy system the reservation needs to be of size one for cpu limit reasons")]
     ReservationArrayShouldBeSizeOne,
+
+    /// This metadata does not have a collection set
+    #[error("This metadata does not have a collection set")]
+    CollectionNotFound,
+
+    /// Collection mint does not match the collection key on this metadata
+    #[error("Collection mint does not match the collection key on this metadata")]
+    CollectionMismatch,
+
+    /// Collection must be a unique master edition with a max supply of 0
+    #[error("Collection must be a unique master edition with a max supply of 0")]
+    CollectionMustBeAUniqueMasterEdition,
+
+    /// Collections can only be verified through the VerifyCollection instruction
+    #[error("Collections can only be verified through the VerifyCollection instruction")]
+    CollectionCannotBeVerifiedInThisInstruction,
+
+    /// Collection is already verified
+    #[error("Collection is already verified")]
+    CollectionAlreadyVerified,
+
+    /// Collection is not verified
+    #[error("Collection is not verified")]
+    CollectionNotVerified,
+
+    /// Pending update authority does not belong to this metadata
+    #[error("Pending update authority does not belong to this metadata")]
+    PendingUpdateAuthorityMetadataMismatch,
+
+    /// Signer is not the proposed new update authority
+    #[error("Signer is not the proposed new update authority")]
+    PendingUpdateAuthorityMismatch,
+
+    /// Update authority changed after this transfer was proposed
+    #[error("Update authority changed after this transfer was proposed")]
+    PendingUpdateAuthorityStale,
+
+    /// Metadata doesn't fit in its account, older fixed size accounts can't grow
+    #[error("Metadata doesn't fit in its account, older fixed size accounts can't grow")]
+    MetadataTooLargeForAccount,
+
+    /// Only fixed size metadata accounts can be puffed
+    #[error("Only fixed size metadata accounts can be puffed")]
+    CannotPuffVariableLengthMetadata,
+
+    /// Master edition has printed editions, its update authority must sign to burn it
+    #[error("Master edition has printed editions, its update authority must sign to burn it")]
+    MasterEditionHasPrints,
+
+    /// This asset does not have uses
+    #[error("This asset does not have uses")]
+    Unusable,
+
+    /// Not enough uses left for this request
+    #[error("Not enough uses left for this request")]
+    NotEnoughUses,
+
+    /// Invalid uses: remaining must equal a nonzero total, and Single totals one
+    #[error("Invalid uses: remaining must equal a nonzero total, and Single totals one")]
+    InvalidUseMethod,
+
+    /// Uses cannot be changed once the asset has been used
+    #[error("Uses cannot be changed once the asset has been used")]
+    CannotChangeUsesAfterFirstUse,
+
+    /// Signer is neither the update authority nor an approved sale authority
+    #[error("Signer is neither the update authority nor an approved sale authority")]
+    InvalidSaleAuthority,
+
+    /// Only unverified creators can be removed by the update authority
+    #[error("Only unverified creators can be removed by the update authority")]
+    CannotRemoveVerifiedCreator,
+
+    /// Print delegate was approved by a previous owner of the master edition token
+    #[error("Print delegate was approved by a previous owner of the master edition token")]
+    InvalidPrintDelegate,
+
+    /// Print delegate has expired
+    #[error("Print delegate has expired")]
+    PrintDelegateExpired,
+
+    /// Print delegate has made all of the prints it was allowed
+    #[error("Print delegate has made all of the prints it was allowed")]
+    PrintDelegateCapReached,
+
+    /// Reserved editions must run forwards from one and stay within max supply
+    #[error("Reserved editions must run forwards from one and stay within max supply")]
+    InvalidEditionReservation,
+
+    /// An edition in this range has already been reserved or printed
+    #[error("An edition in this range has already been reserved or printed")]
+    EditionAlreadyReserved,
+
+    /// This edition is not reserved by this edition reservation account
+    #[error("This edition is not reserved by this edition reservation account")]
+    EditionNotReserved,
+
+    /// Reserved editions must be printed to a token account of their recipient
+    #[error("Reserved editions must be printed to a token account of their recipient")]
+    ReservedEditionRecipientMismatch,
+
+    /// Reservation list belongs to a different master edition
+    #[error("Reservation list belongs to a different master edition")]
+    ReservationListMasterEditionMismatch,
+
+    /// Royalties can only be enforced when the mint's freeze authority is its edition
+    #[error("Royalties can only be enforced when the mint's freeze authority is its edition")]
+    FreezeAuthorityNotEdition,
+
+    /// Too many allowed transfer programs
+    #[error("Too many allowed transfer programs")]
+    TooManyAllowedTransferPrograms,
+
+    /// Royalties are not enforced on this metadata
+    #[error("Royalties are not enforced on this metadata")]
+    RoyaltyEnforcementNotEnabled,
+
+    /// Royalty enforced tokens can only be moved by an allowed transfer program
+    #[error("Royalty enforced tokens can only be moved by an allowed transfer program")]
+    TransferProgramNotAllowed,
+
+    /// Mint decimals do not match the token standard
+    #[error("Mint decimals do not match the token standard")]
+    MintDecimalsDoNotMatchTokenStandard,
+
+    /// Fungible tokens can't have creators or royalties
+    #[error("Fungible tokens can't have creators or royalties")]
+    FungibleCannotHaveCreatorsOrRoyalties,
+
+    /// Fungible tokens can't be master editions
+    #[error("Fungible tokens can't be master editions")]
+    FungibleCannotBeMasterEdition,
+
+    /// Edition marker still records printed editions
+    #[error("Edition marker still records printed editions")]
+    EditionMarkerInUse,
+
+    /// Reservation lists can't be closed while their master edition is a V1
+    #[error("Reservation lists can't be closed while their master edition is a V1")]
+    ReservationListInUse,
+
+    /// Is mutable can only be flipped to false
+    #[error("Is mutable can only be flipped to false")]
+    IsMutableCanOnlyBeFlippedToFalse,
+
+    /// A verified collection must be unverified before it can be changed
+    #[error("A verified collection must be unverified before it can be changed")]
+    VerifiedCollectionCannotBeChanged,
+
+    /// Rent from a pending update authority can only go back to the payer that funded it
+    #[error("Rent from a pending update authority can only go back to the payer that funded it")]
+    PendingUpdateAuthorityPayerMismatch,
+
+    /// Edition template doesn't fit in the master edition
+    #[error("Edition template doesn't fit in the master edition")]
+    EditionTemplateTooLong,
+
+    /// Edition reservation has no room for another range
+    #[error("Edition reservation has no room for another range")]
+    EditionReservationFull,
+
+    /// Royalties can only be enforced by freezing the token account holding the token
+    #[error("Royalties can only be enforced by freezing the token account holding the token")]
+    EnforcedTokenNotHeld,
 }
 
 impl PrintProgramError for MetadataError {
@@ -1,10 +1,16 @@
 use {
     crate::{
         deprecated_instruction::{MintPrintingTokensViaTokenArgs, SetReservationListArgs},
-        state::{Creator, Data, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX},
+        state::{
+            Collection, Creator, Data, DataV2, EditionTemplate, TokenStandard, Uses, BURN, EDITION,
+            EDITION_MARKER_BIT_SIZE, EDITION_RESERVATION, LEGACY_PRINTING,
+            PENDING_UPDATE_AUTHORITY, PREFIX, PRINT_DELEGATE, SALE_AUTHORITY, TRANSFER_AUTHORITY,
+            USER,
+        },
     },
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
+        clock::UnixTimestamp,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         sysvar,
@@ -30,6 +36,37 @@ pub struct CreateMetadataAccountArgs {
     pub is_mutable: bool,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Args for update call, v2 with collection support
+pub struct UpdateMetadataAccountArgsV2 {
+    pub data: Option<DataV2>,
+    pub update_authority: Option<Pubkey>,
+    pub primary_sale_happened: Option<bool>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Args for create call, v2 with collection support
+pub struct CreateMetadataAccountArgsV2 {
+    /// Note that unique metadatas are disabled for now.
+    pub data: DataV2,
+    /// Whether you want your metadata to be updateable in the future.
+    pub is_mutable: bool,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Args for create call, v3 with a token standard
+pub struct CreateMetadataAccountArgsV3 {
+    /// Note that unique metadatas are disabled for now.
+    pub data: DataV2,
+    /// Whether you want your metadata to be updateable in the future.
+    pub is_mutable: bool,
+    /// What kind of token the mint is, which decides the data it can have.
+    pub token_standard: TokenStandard,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct CreateMasterEditionArgs {
@@ -43,6 +80,102 @@ pub struct MintNewEditionFromMasterEditionViaTokenArgs {
     pub edition: u64,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ApproveUseAuthorityArgs {
+    /// How many of the asset's remaining uses the use authority may spend.
+    pub number_of_uses: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UtilizeArgs {
+    pub number_of_uses: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct RemoveUnverifiedCreatorsArgs {
+    /// Unverified creators to drop, their shares go to the creators that remain.
+    pub creators: Vec<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct MintNewEditionsFromMasterEditionViaTokenArgs {
+    /// Edition number of the first print, the rest follow consecutively
+    pub first_edition: u64,
+    /// Number of prints, each with its own new metadata, edition and mint accounts
+    pub count: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ReserveEditionsArgs {
+    /// First edition number to reserve
+    pub start: u64,
+    /// Last edition number to reserve, inclusive
+    pub end: u64,
+    /// Wallet the reserved editions may only be printed to
+    pub recipient: Pubkey,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ApprovePrintDelegateArgs {
+    /// Most prints the delegate may make, unlimited if None.
+    pub max_prints: Option<u64>,
+    /// Unix timestamp after which the delegate may no longer print, never if None.
+    pub expiry: Option<UnixTimestamp>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct FreezeMetadataArgs {
+    /// Hash of the JSON the uri points at, replacing any set at creation.
+    pub content_hash: Option<[u8; 32]>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetEditionTemplateArgs {
+    /// Name and uri for future prints, None to go back to copying the master's.
+    pub edition_template: Option<EditionTemplate>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetRoyaltyEnforcementArgs {
+    /// Programs sales have to go through, None to stop enforcing royalties.
+    pub allowed_transfer_programs: Option<Vec<Pubkey>>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Args for patch call, fields left as None are not changed
+pub struct PatchMetadataAccountArgs {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub seller_fee_basis_points: Option<u16>,
+    /// Creators to add or give a new share. Creators keep their verified flag, only the update
+    /// authority can mark itself verified.
+    pub creators: Option<Vec<Creator>>,
+    /// Creators to drop, verified or not
+    pub removed_creators: Option<Vec<Pubkey>>,
+    /// Can only be flipped to false
+    pub is_mutable: Option<bool>,
+    /// Proposed, not set: the new update authority has to accept it
+    pub update_authority: Option<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct CloseEditionAccountsArgs {
+    /// Numbers of the edition markers being closed, in the order their accounts are passed.
+    pub edition_marker_numbers: Vec<u64>,
+}
+
 /// Instructions supported by the Metadata program.
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub enum MetadataInstruction {
@@ -59,6 +192,8 @@ pub enum MetadataInstruction {
     /// Update a Metadata
     ///   0. `[writable]` Metadata account
     ///   1. `[signer]` Update authority key
+    /// A new update authority is set right away without its signature, use ProposeUpdateAuthority
+    /// to have it accept the metadata first.
     UpdateMetadataAccount(UpdateMetadataAccountArgs),
 
     /// Register a Metadata as a Master Edition V1, which means Editions can be minted.
@@ -205,6 +340,8 @@ pub enum MetadataInstruction {
     ///   11. `[]` Token program
     ///   12. `[]` System program
     ///   13. `[]` Rent info
+    ///   14. `[writable]` Optional print delegate record (pda of ['metadata', program id, master metadata mint id, 'print_delegate', delegate])
+    ///       when given, #7 may be that print delegate instead of the owner of the token account.
     MintNewEditionFromMasterEditionViaToken(MintNewEditionFromMasterEditionViaTokenArgs),
 
     /// Converts the Master Edition V1 to a Master Edition V2, draining lamports from the two printing mints
@@ -240,8 +377,373 @@ pub enum MetadataInstruction {
 
     /// Puff a Metadata - make all of it's variable length fields (name/uri/symbol) a fixed length using a null character
     /// so that it can be found using offset searches by the RPC to make client lookups cheaper.
+    /// Only applies to older, fixed size (MAX_METADATA_LEN) accounts.
     ///   0. `[writable]` Metadata account
     PuffMetadata,
+
+    /// Create Metadata object with an optional, unverified collection.
+    ///   0. `[writable]`  Metadata key (pda of ['metadata', program id, mint id])
+    ///   1. `[]` Mint of token asset
+    ///   2. `[signer]` Mint authority
+    ///   3. `[signer]` payer
+    ///   4. `[]` update authority info
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    CreateMetadataAccountV2(CreateMetadataAccountArgsV2),
+
+    /// Update a Metadata, including its collection. A verified collection must be unverified before it can be changed.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority key
+    ///   2. `[signer]` New update authority key, only when the update authority changes
+    UpdateMetadataAccountV2(UpdateMetadataAccountArgsV2),
+
+    /// Verify that a Metadata belongs to the collection it names.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority of the collection's metadata
+    ///   2. `[]` Mint of the collection
+    ///   3. `[]` Metadata account of the collection
+    ///   4. `[]` Master Edition account of the collection (pda of ['metadata', program id, collection mint id, 'edition'])
+    VerifyCollection,
+
+    /// Remove the verified flag from a Metadata's collection.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority of the collection's metadata
+    ///   2. `[]` Mint of the collection
+    ///   3. `[]` Metadata account of the collection
+    ///   4. `[]` Master Edition account of the collection (pda of ['metadata', program id, collection mint id, 'edition'])
+    UnverifyCollection,
+
+    /// Propose handing the update authority of a Metadata to a new key. Nothing changes until
+    /// the new key signs AcceptUpdateAuthority. Proposing again overwrites the pending key.
+    ///   0. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   1. `[]` Metadata account
+    ///   2. `[signer]` Current update authority
+    ///   3. `[]` Proposed new update authority
+    ///   4. `[signer]` Payer
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    ProposeUpdateAuthority,
+
+    /// Accept a proposed update authority transfer, closing the pending record and refunding its rent to the payer that funded it.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   2. `[signer]` Proposed new update authority
+    ///   3. `[writable]` Payer that funded the pending update authority
+    AcceptUpdateAuthority,
+
+    /// Cancel a proposed update authority transfer, closing the pending record and refunding its rent to the payer that funded it.
+    ///   0. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   1. `[]` Metadata account
+    ///   2. `[signer]` Current update authority
+    ///   3. `[writable]` Payer that funded the pending update authority
+    CancelUpdateAuthority,
+
+    /// Burn an NFT, closing its token account, Metadata and Edition or Master Edition and sending
+    /// all of their rent to the owner. A print also has its bit cleared in its edition marker.
+    ///   0. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   1. `[signer, writable]` Owner of the token account
+    ///   2. `[writable]` Mint of the NFT
+    ///   3. `[writable]` Token account holding the NFT
+    ///   4. `[writable]` Master Edition V2 or Edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   5. `[]` Token program
+    /// For a Master Edition V2 that has printed editions:
+    ///   6. `[signer]` Update authority of the Metadata, allowing the prints to outlive their master
+    /// For an Edition:
+    ///   6. `[]` Master Edition of the print (pda of ['metadata', program id, master mint id, 'edition'])
+    ///   7. `[]` Mint of the Master Edition
+    ///   8. `[writable]` Edition marker (pda of ['metadata', program id, master mint id, 'edition', edition_number])
+    ///       where edition_number is NOT the edition number you pass in args but actually edition / EDITION_MARKER_BIT_SIZE.
+    BurnNft,
+
+    /// Let another key spend uses on an asset with uses. Approving again overwrites the allowance.
+    /// For assets that burn on their last use the burner pda is also made delegate of the token.
+    ///   0. `[writable]` Use authority record (pda of ['metadata', program id, mint id, 'user', owner, use authority])
+    ///   1. `[signer]` Owner of the token account
+    ///   2. `[signer]` Payer
+    ///   3. `[]` Use authority
+    ///   4. `[writable]` Token account holding the asset
+    ///   5. `[]` Metadata account
+    ///   6. `[]` Burner (pda of ['metadata', program id, 'burn'])
+    ///   7. `[]` Token program
+    ///   8. `[]` System program
+    ///   9. `[]` Rent info
+    ApproveUseAuthority(ApproveUseAuthorityArgs),
+
+    /// Revoke a use authority, closing its record and refunding its rent to the owner.
+    ///   0. `[writable]` Use authority record (pda of ['metadata', program id, mint id, 'user', owner, use authority])
+    ///   1. `[signer, writable]` Owner of the token account
+    ///   2. `[]` Use authority
+    ///   3. `[writable]` Token account holding the asset
+    ///   4. `[]` Metadata account
+    ///   5. `[]` Token program
+    RevokeUseAuthority,
+
+    /// Spend uses on an asset. If it burns on use, the token is burned once no uses remain.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[writable]` Token account holding the asset
+    ///   2. `[writable]` Mint of the asset
+    ///   3. `[signer]` Owner of the token account, or an approved use authority
+    ///   4. `[]` Owner of the token account
+    ///   5. `[]` Token program
+    /// When signed by a use authority:
+    ///   6. `[writable]` Use authority record (pda of ['metadata', program id, mint id, 'user', owner, use authority])
+    ///   7. `[]` Burner (pda of ['metadata', program id, 'burn'])
+    Utilize(UtilizeArgs),
+
+    /// Let another key mark the primary sale of a Metadata as happened, for instance the pda of a
+    /// marketplace that holds tokens in escrow and so can't go through UpdatePrimarySaleHappenedViaToken.
+    ///   0. `[writable]` Sale authority record (pda of ['metadata', program id, mint id, 'sale_authority', sale authority])
+    ///   1. `[]` Metadata account
+    ///   2. `[signer]` Update authority
+    ///   3. `[]` Sale authority
+    ///   4. `[signer]` Payer
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    ApproveSaleAuthority,
+
+    /// Revoke a sale authority, closing its record and refunding its rent to the update authority.
+    ///   0. `[writable]` Sale authority record (pda of ['metadata', program id, mint id, 'sale_authority', sale authority])
+    ///   1. `[]` Metadata account
+    ///   2. `[signer, writable]` Update authority
+    ///   3. `[]` Sale authority
+    RevokeSaleAuthority,
+
+    /// Mark the primary sale of a Metadata as happened, signed by its update authority or an approved sale authority.
+    /// Like UpdatePrimarySaleHappenedViaToken this can never set it back to false.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority or sale authority
+    /// When signed by a sale authority:
+    ///   2. `[]` Sale authority record (pda of ['metadata', program id, mint id, 'sale_authority', sale authority])
+    UpdatePrimarySaleHappenedViaAuthority,
+
+    /// Permanently lock a Metadata by setting is_mutable to false, optionally recording the hash
+    /// of the JSON its uri points at so collectors can check the content never changes.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority key
+    FreezeMetadata(FreezeMetadataArgs),
+
+    /// Sign many pieces of metadata that have you as an unverified creator so that they are now verified.
+    /// Fails as a whole if any of them can't be signed, and logs each metadata that was.
+    ///   0. `[signer]` Creator
+    ///   1..n `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    SignMetadataBatch,
+
+    /// Remove your verification from a piece of metadata that lists you as a creator.
+    ///   0. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   1. `[signer]` Creator
+    RemoveCreatorVerification,
+
+    /// Drop unverified creators from a mutable Metadata, handing their shares to the creators that
+    /// remain in proportion to what they already hold.
+    ///   0. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   1. `[signer]` Update authority
+    RemoveUnverifiedCreators(RemoveUnverifiedCreatorsArgs),
+
+    /// Let another key print editions from a Master Edition V2 through MintNewEditionFromMasterEditionViaToken
+    /// without holding its token, optionally capped and expiring. Approving again overwrites the record.
+    /// The record lapses if the master edition token changes hands.
+    ///   0. `[writable]` Print delegate record (pda of ['metadata', program id, master metadata mint id, 'print_delegate', delegate])
+    ///   1. `[signer]` Owner of the token account holding the master edition token
+    ///   2. `[]` Token account holding the master edition token
+    ///   3. `[]` Master record metadata account
+    ///   4. `[]` Print delegate
+    ///   5. `[signer]` Payer
+    ///   6. `[]` System program
+    ///   7. `[]` Rent info
+    ApprovePrintDelegate(ApprovePrintDelegateArgs),
+
+    /// Revoke a print delegate, closing its record and refunding its rent to the owner who approved it.
+    ///   0. `[writable]` Print delegate record (pda of ['metadata', program id, master metadata mint id, 'print_delegate', delegate])
+    ///   1. `[signer, writable]` Owner who approved the print delegate
+    ///   2. `[]` Master record metadata account
+    ///   3. `[]` Print delegate
+    RevokePrintDelegate,
+
+    /// Reserve a range of edition numbers of a Master Edition V2 for a recipient. Reserved numbers are marked as
+    /// taken on their EditionMarkers, so only MintReservedEditionFromMasterEditionViaToken can print them, to a
+    /// token account of the new mint owned by the recipient.
+    ///   0. `[writable]` Edition reservation (pda of ['metadata', program id, master metadata mint id, 'edition_reservation'])
+    ///   1. `[signer]` Update authority of the master metadata
+    ///   2. `[]` Master record metadata account
+    ///   3. `[]` Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   4. `[signer, writable]` Payer
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    ///   7+. `[writable]` Edition markers for every marker the range touches, in order (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///       where edition_number is NOT the edition number you pass in args but actually edition number = floor(edition/EDITION_MARKER_BIT_SIZE).
+    ReserveEditions(ReserveEditionsArgs),
+
+    /// Print several consecutive editions of a Master Edition V2 at once, as MintNewEditionFromMasterEditionViaToken
+    /// would one at a time. Each edition marker is written once and the master edition's supply is bumped once.
+    ///   0. `[writable]` Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   1. `[signer]` Mint authority of every new mint
+    ///   2. `[signer]` payer
+    ///   3. `[signer]` owner of token account containing master token (#4)
+    ///   4. `[]` token account containing token from master metadata mint
+    ///   5. `[]` Update authority info for new metadata
+    ///   6. `[]` Master record metadata account
+    ///   7. `[]` Token program
+    ///   8. `[]` System program
+    ///   9. `[]` Rent info
+    ///   10+. `[writable]` Edition markers for every marker the printed editions touch, in order (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///       where edition_number = floor(edition/EDITION_MARKER_BIT_SIZE).
+    ///   Then for each print, in edition order:
+    ///   `[writable]` New Metadata key (pda of ['metadata', program id, mint id])
+    ///   `[writable]` New Edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   `[writable]` Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
+    MintNewEditionsFromMasterEditionViaToken(MintNewEditionsFromMasterEditionViaTokenArgs),
+
+    /// Migrate a Master Edition V1 to V2 even while Printing tokens are outstanding. Editions 1 through supply are
+    /// marked as taken on their EditionMarkers, unprinted spots of the given reservation lists move to an edition
+    /// reservation for the same addresses, and a legacy printing record lets outstanding Printing tokens be burned
+    /// through MintNewEditionFromMasterEditionViaLegacyPrintingToken. One Time Printing Authorization tokens can no
+    /// longer mint Printing tokens afterwards.
+    ///   0. `[writable]` Master Record Edition V1 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   1. `[]` Master record metadata account
+    ///   2. `[signer]` Update authority of the master metadata
+    ///   3. `[writable]` Legacy printing record (pda of ['metadata', program id, master metadata mint id, 'legacy_printing'])
+    ///   4. `[writable]` Edition reservation (pda of ['metadata', program id, master metadata mint id, 'edition_reservation'])
+    ///   5. `[signer, writable]` Payer
+    ///   6. `[]` System program
+    ///   7. `[]` Rent info
+    ///   8+. `[writable]` Edition markers for editions 1 through supply, in order (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///       where edition_number = floor(edition/EDITION_MARKER_BIT_SIZE), none if supply is zero.
+    ///   Then `[]` every Reservation List V1 or V2 of the master edition whose spots should stay reserved
+    MigrateMasterEditionV1ToV2,
+
+    /// Burn a Printing token of a master edition migrated with MigrateMasterEditionV1ToV2 for a print,
+    /// the way MintNewEditionFromMasterEditionViaToken prints for the holder of the master token.
+    ///   0. `[writable]` New Metadata key (pda of ['metadata', program id, mint id])
+    ///   1. `[writable]` New Edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   2. `[writable]` Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   3. `[writable]` Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
+    ///   4. `[writable]` Edition pda to mark creation - will be checked for pre-existence. (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///   where edition_number is NOT the edition number you pass in args but actually edition_number = floor(edition/EDITION_MARKER_BIT_SIZE).
+    ///   5. `[signer]` Mint authority of new mint
+    ///   6. `[signer]` payer
+    ///   7. `[signer]` owner of token account containing a Printing token (#8)
+    ///   8. `[writable]` token account containing a Printing token, which is burned
+    ///   9. `[]` Update authority info for new metadata
+    ///   10. `[]` Master record metadata account
+    ///   11. `[]` Token program
+    ///   12. `[]` System program
+    ///   13. `[]` Rent info
+    ///   14. `[writable]` Printing mint
+    ///   15. `[]` Legacy printing record (pda of ['metadata', program id, master metadata mint id, 'legacy_printing'])
+    ///   16. `[writable]` Optional edition reservation (pda of ['metadata', program id, master metadata mint id, 'edition_reservation'])
+    ///   17. `[]` Optional token account of the new mint, owned by the recipient of the reserved edition and holding its one token
+    MintNewEditionFromMasterEditionViaLegacyPrintingToken(
+        MintNewEditionFromMasterEditionViaTokenArgs,
+    ),
+
+    /// Set or clear the template prints of a master edition take their name and uri from, in place of
+    /// the master's own. The edition number is written wherever EDITION_NUMBER_PLACEHOLDER appears.
+    /// The template is stored on the master edition, so it has to fit in MAX_EDITION_TEMPLATE_LEN.
+    ///   0. `[]` Metadata account of the master edition
+    ///   1. `[signer]` Update authority key
+    ///   2. `[writable]` Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])
+    SetEditionTemplate(SetEditionTemplateArgs),
+
+    /// Opt in to enforced royalties by recording the programs sales have to go through, or opt back out.
+    /// Opting in freezes the token account holding the token, and while enforced only an allowed program
+    /// can move it with TransferEnforcedToken, which freezes it again in its new account, so the token
+    /// can't change hands without paying royalties. The mint's freeze authority must be its edition to opt in.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority key
+    ///   2. `[]` Mint of the metadata
+    ///   3. `[]` Master Edition or Edition of the mint (pda of ['metadata', program id, mint id, 'edition'])
+    ///   4. `[writable]` Token account holding the token - only used when opting in
+    ///   5. `[]` Token program
+    SetRoyaltyEnforcement(SetRoyaltyEnforcementArgs),
+
+    /// Freeze a token account of a mint whose metadata enforces royalties. Needs no signer, so anyone can
+    /// crank it, for example after an owner thawed their token while royalties weren't enforced.
+    ///   0. `[writable]` Token account
+    ///   1. `[]` Metadata account
+    ///   2. `[]` Mint of the metadata
+    ///   3. `[]` Master Edition or Edition of the mint (pda of ['metadata', program id, mint id, 'edition'])
+    ///   4. `[]` Token program
+    FreezeEnforcedToken,
+
+    /// Thaw a token account of a mint whose metadata no longer enforces royalties, as its owner. While
+    /// royalties are enforced a token is only thawed inside TransferEnforcedToken.
+    ///   0. `[writable]` Token account
+    ///   1. `[]` Metadata account
+    ///   2. `[]` Mint of the metadata
+    ///   3. `[]` Master Edition or Edition of the mint (pda of ['metadata', program id, mint id, 'edition'])
+    ///   4. `[]` Token program
+    ///   5. `[signer]` Owner of the token account
+    ThawEnforcedToken,
+
+    /// Create Metadata object recording the mint's token standard. NonFungible and FungibleAsset mints
+    /// must have no decimals, and Fungible metadata can't have creators, royalties or a master edition.
+    ///   0. `[writable]`  Metadata key (pda of ['metadata', program id, mint id])
+    ///   1. `[]` Mint of token asset
+    ///   2. `[signer]` Mint authority
+    ///   3. `[signer]` payer
+    ///   4. `[]` update authority info
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    CreateMetadataAccountV3(CreateMetadataAccountArgsV3),
+
+    /// Close edition markers and reservation lists of a master edition that can no longer be used,
+    /// returning their rent to its update authority. Edition markers can only be closed once none of
+    /// their editions are taken, and reservation lists once the master edition is no longer a V1.
+    ///   0. `[]` Master Edition V1 or V2 (pda of ['metadata', program id, master mint id, 'edition'])
+    ///   1. `[]` Master metadata
+    ///   2. `[writable, signer]` Update authority of the master metadata
+    ///   3. `[writable]` One edition marker (pda of ['metadata', program id, master mint id, 'edition', marker number])
+    ///      per number in the args, in the same order, followed by any reservation lists to close
+    CloseEditionAccounts(CloseEditionAccountsArgs),
+
+    /// Update only the given fields of a Metadata, leaving the rest, and the verified flags of its
+    /// creators, as they are on chain. A new update authority is only proposed, as with
+    /// ProposeUpdateAuthority, and takes over once it accepts with AcceptUpdateAuthority.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority key
+    ///   2. `[signer, writable]` Payer - funds the pending update authority record
+    ///   3. `[]` System program
+    ///   4. `[writable]` Optional pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///      - required when proposing a new update authority
+    ///   5. `[]` Optional rent info - required along with the pending update authority
+    PatchMetadataAccount(PatchMetadataAccountArgs),
+
+    /// Print an edition number reserved with ReserveEditions, as MintNewEditionFromMasterEditionViaToken prints
+    /// unreserved ones. An edition number that was never reserved is printed as usual.
+    ///   0. `[writable]` New Metadata key (pda of ['metadata', program id, mint id])
+    ///   1. `[writable]` New Edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   2. `[writable]` Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   3. `[writable]` Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
+    ///   4. `[writable]` Edition pda to mark creation (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///   where edition_number is NOT the edition number you pass in args but actually edition_number = floor(edition/EDITION_MARKER_BIT_SIZE).
+    ///   5. `[signer]` Mint authority of new mint
+    ///   6. `[signer]` payer
+    ///   7. `[signer]` owner of token account containing master token (#8)
+    ///   8. `[]` token account containing token from master metadata mint
+    ///   9. `[]` Update authority info for new metadata
+    ///   10. `[]` Master record metadata account
+    ///   11. `[]` Token program
+    ///   12. `[]` System program
+    ///   13. `[]` Rent info
+    ///   14. `[writable]` Edition reservation (pda of ['metadata', program id, master metadata mint id, 'edition_reservation'])
+    ///   15. `[]` Token account of the new mint, owned by the recipient of the reserved edition and holding its one token
+    ///   16. `[writable]` Optional print delegate record (pda of ['metadata', program id, master metadata mint id, 'print_delegate', delegate])
+    ///       when given, #7 may be that print delegate instead of the owner of the token account.
+    MintReservedEditionFromMasterEditionViaToken(MintNewEditionFromMasterEditionViaTokenArgs),
+
+    /// Move a token of a mint whose metadata enforces royalties for an allowed program, which is trusted
+    /// to have paid the creators. The source is thawed for the transfer and the destination is frozen
+    /// before the instruction returns, so the token never leaves the instruction unfrozen.
+    ///   0. `[writable]` Source token account, holding the token
+    ///   1. `[writable]` Destination token account, frozen once the token is in it
+    ///   2. `[]` Metadata account
+    ///   3. `[]` Mint of the metadata
+    ///   4. `[]` Master Edition or Edition of the mint (pda of ['metadata', program id, mint id, 'edition'])
+    ///   5. `[]` Token program
+    ///   6. `[signer]` Transfer authority of the allowed program (pda of ['transfer_authority'] under that program)
+    ///   7. `[]` Allowed transfer program
+    ///   8. `[signer]` Optional owner or delegate of the source token account, when that isn't the transfer authority
+    TransferEnforcedToken,
 }
 
 /// Creates an CreateMetadataAccounts instruction
@@ -518,3 +1020,1326 @@ pub fn mint_edition_from_master_edition_via_vault_proxy(
         .unwrap(),
     }
 }
+
+/// Creates an CreateMetadataAccountV2 instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v2(
+    program_id: Pubkey,
+    metadata_account: Pubkey,
+    mint: Pubkey,
+    mint_authority: Pubkey,
+    payer: Pubkey,
+    update_authority: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    creators: Option<Vec<Creator>>,
+    seller_fee_basis_points: u16,
+    update_authority_is_signer: bool,
+    is_mutable: bool,
+    collection: Option<Collection>,
+    uses: Option<Uses>,
+    content_hash: Option<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_account, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(mint_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(update_authority, update_authority_is_signer),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::CreateMetadataAccountV2(CreateMetadataAccountArgsV2 {
+            data: DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points,
+                creators,
+                collection,
+                uses,
+                content_hash,
+            },
+            is_mutable,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// update metadata account v2 instruction
+pub fn update_metadata_accounts_v2(
+    program_id: Pubkey,
+    metadata_account: Pubkey,
+    update_authority: Pubkey,
+    new_update_authority: Option<Pubkey>,
+    data: Option<DataV2>,
+    primary_sale_happened: Option<bool>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata_account, false),
+        AccountMeta::new_readonly(update_authority, true),
+    ];
+    if let Some(new_update_authority) = new_update_authority {
+        accounts.push(AccountMeta::new_readonly(new_update_authority, true));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::UpdateMetadataAccountV2(UpdateMetadataAccountArgsV2 {
+            data,
+            update_authority: new_update_authority,
+            primary_sale_happened,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Verify Collection
+#[allow(clippy::too_many_arguments)]
+pub fn verify_collection(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(collection_authority, true),
+            AccountMeta::new_readonly(collection_mint, false),
+            AccountMeta::new_readonly(collection, false),
+            AccountMeta::new_readonly(collection_master_edition_account, false),
+        ],
+        data: MetadataInstruction::VerifyCollection.try_to_vec().unwrap(),
+    }
+}
+
+/// Unverify Collection
+#[allow(clippy::too_many_arguments)]
+pub fn unverify_collection(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(collection_authority, true),
+            AccountMeta::new_readonly(collection_mint, false),
+            AccountMeta::new_readonly(collection, false),
+            AccountMeta::new_readonly(collection_master_edition_account, false),
+        ],
+        data: MetadataInstruction::UnverifyCollection
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Derives the pending update authority pda for a mint
+pub fn find_pending_update_authority_address(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Propose Update Authority
+#[allow(clippy::too_many_arguments)]
+pub fn propose_update_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    new_update_authority: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_pending_update_authority_address(&program_id, &mint),
+                false,
+            ),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(new_update_authority, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::ProposeUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Accept Update Authority
+pub fn accept_update_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    new_update_authority: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(
+                find_pending_update_authority_address(&program_id, &mint),
+                false,
+            ),
+            AccountMeta::new_readonly(new_update_authority, true),
+            AccountMeta::new(payer, false),
+        ],
+        data: MetadataInstruction::AcceptUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Cancel Update Authority
+pub fn cancel_update_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_pending_update_authority_address(&program_id, &mint),
+                false,
+            ),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, false),
+        ],
+        data: MetadataInstruction::CancelUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Burn a Master Edition V2 NFT, or an NFT with an Edition that isn't a print.
+/// The update authority is only needed if the master edition has printed editions.
+pub fn burn_nft(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    master_edition: Pubkey,
+    update_authority: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(owner, true),
+        AccountMeta::new(mint, false),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    if let Some(update_authority) = update_authority {
+        accounts.push(AccountMeta::new_readonly(update_authority, true));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::BurnNft.try_to_vec().unwrap(),
+    }
+}
+
+/// Burn a print Edition NFT, freeing its edition number on the master edition.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_print_edition(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    edition: Pubkey,
+    master_edition: Pubkey,
+    master_mint: Pubkey,
+    edition_number: u64,
+) -> Instruction {
+    let edition_marker_number = edition_number.checked_div(EDITION_MARKER_BIT_SIZE).unwrap();
+    let as_string = edition_marker_number.to_string();
+    let (edition_mark_pda, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(edition, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(master_edition, false),
+            AccountMeta::new_readonly(master_mint, false),
+            AccountMeta::new(edition_mark_pda, false),
+        ],
+        data: MetadataInstruction::BurnNft.try_to_vec().unwrap(),
+    }
+}
+
+/// Derives the use authority record pda for a mint, the owner that approved it and the use authority
+pub fn find_use_authority_record_address(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    use_authority: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            USER.as_bytes(),
+            owner.as_ref(),
+            use_authority.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Derives the pda that burns assets on behalf of use authorities
+pub fn find_burner_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), BURN.as_bytes()],
+        program_id,
+    )
+    .0
+}
+
+/// Approve Use Authority
+#[allow(clippy::too_many_arguments)]
+pub fn approve_use_authority(
+    program_id: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    use_authority: Pubkey,
+    token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    number_of_uses: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_use_authority_record_address(&program_id, &mint, &owner, &use_authority),
+                false,
+            ),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(use_authority, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(find_burner_address(&program_id), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::ApproveUseAuthority(ApproveUseAuthorityArgs { number_of_uses })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Revoke Use Authority
+pub fn revoke_use_authority(
+    program_id: Pubkey,
+    owner: Pubkey,
+    use_authority: Pubkey,
+    token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_use_authority_record_address(&program_id, &mint, &owner, &use_authority),
+                false,
+            ),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(use_authority, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::RevokeUseAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Utilize, signed by either the owner or an approved use authority
+pub fn utilize(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    token_account: Pubkey,
+    mint: Pubkey,
+    use_authority: Pubkey,
+    owner: Pubkey,
+    number_of_uses: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new_readonly(use_authority, true),
+        AccountMeta::new_readonly(owner, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    if use_authority != owner {
+        accounts.push(AccountMeta::new(
+            find_use_authority_record_address(&program_id, &mint, &owner, &use_authority),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_burner_address(&program_id),
+            false,
+        ));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::Utilize(UtilizeArgs { number_of_uses })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Derives the sale authority record pda for a mint and sale authority
+pub fn find_sale_authority_record_address(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    sale_authority: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            SALE_AUTHORITY.as_bytes(),
+            sale_authority.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Approve Sale Authority
+pub fn approve_sale_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    sale_authority: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_sale_authority_record_address(&program_id, &mint, &sale_authority),
+                false,
+            ),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(sale_authority, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::ApproveSaleAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Revoke Sale Authority
+pub fn revoke_sale_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    sale_authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_sale_authority_record_address(&program_id, &mint, &sale_authority),
+                false,
+            ),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new(update_authority, true),
+            AccountMeta::new_readonly(sale_authority, false),
+        ],
+        data: MetadataInstruction::RevokeSaleAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Update primary sale happened via the update authority, or a sale authority when its record is given
+pub fn update_primary_sale_happened_via_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    authority: Pubkey,
+    sale_authority_record: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(authority, true),
+    ];
+
+    if let Some(sale_authority_record) = sale_authority_record {
+        accounts.push(AccountMeta::new_readonly(sale_authority_record, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::UpdatePrimarySaleHappenedViaAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Freeze metadata, optionally recording its content hash
+pub fn freeze_metadata(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    content_hash: Option<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+        ],
+        data: MetadataInstruction::FreezeMetadata(FreezeMetadataArgs { content_hash })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Sign many metadata as the same creator
+pub fn sign_metadata_batch(
+    program_id: Pubkey,
+    creator: Pubkey,
+    metadata: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(creator, true)];
+    accounts.extend(metadata.iter().map(|key| AccountMeta::new(*key, false)));
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SignMetadataBatch.try_to_vec().unwrap(),
+    }
+}
+
+/// Remove creator verification
+pub fn remove_creator_verification(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    creator: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(creator, true),
+        ],
+        data: MetadataInstruction::RemoveCreatorVerification
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Remove unverified creators
+pub fn remove_unverified_creators(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    creators: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+        ],
+        data: MetadataInstruction::RemoveUnverifiedCreators(RemoveUnverifiedCreatorsArgs {
+            creators,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Derives the print delegate record pda for a master edition's mint and delegate
+pub fn find_print_delegate_record_address(
+    program_id: &Pubkey,
+    master_mint: &Pubkey,
+    print_delegate: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            PRINT_DELEGATE.as_bytes(),
+            print_delegate.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Approve Print Delegate
+#[allow(clippy::too_many_arguments)]
+pub fn approve_print_delegate(
+    program_id: Pubkey,
+    owner: Pubkey,
+    token_account: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    print_delegate: Pubkey,
+    payer: Pubkey,
+    max_prints: Option<u64>,
+    expiry: Option<UnixTimestamp>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_print_delegate_record_address(&program_id, &metadata_mint, &print_delegate),
+                false,
+            ),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new_readonly(token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(print_delegate, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::ApprovePrintDelegate(ApprovePrintDelegateArgs {
+            max_prints,
+            expiry,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Revoke Print Delegate
+pub fn revoke_print_delegate(
+    program_id: Pubkey,
+    owner: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    print_delegate: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_print_delegate_record_address(&program_id, &metadata_mint, &print_delegate),
+                false,
+            ),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(print_delegate, false),
+        ],
+        data: MetadataInstruction::RevokePrintDelegate
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Mint a new edition from a master edition as its print delegate, rather than the holder of its token
+#[allow(clippy::too_many_arguments)]
+pub fn mint_new_edition_from_master_edition_via_print_delegate(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    print_delegate: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let mut instruction = mint_new_edition_from_master_edition_via_token(
+        program_id,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint,
+        new_mint_authority,
+        payer,
+        print_delegate,
+        token_account,
+        new_metadata_update_authority,
+        metadata,
+        metadata_mint,
+        edition,
+    );
+    instruction.accounts.push(AccountMeta::new(
+        find_print_delegate_record_address(&program_id, &metadata_mint, &print_delegate),
+        false,
+    ));
+
+    instruction
+}
+
+/// Derives the edition reservation pda for a master edition's mint
+pub fn find_edition_reservation_address(program_id: &Pubkey, master_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION_RESERVATION.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Reserve Editions
+#[allow(clippy::too_many_arguments)]
+pub fn reserve_editions(
+    program_id: Pubkey,
+    update_authority: Pubkey,
+    metadata: Pubkey,
+    master_edition: Pubkey,
+    metadata_mint: Pubkey,
+    payer: Pubkey,
+    start: u64,
+    end: u64,
+    recipient: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(
+            find_edition_reservation_address(&program_id, &metadata_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(master_edition, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    for marker in start / EDITION_MARKER_BIT_SIZE..=end / EDITION_MARKER_BIT_SIZE {
+        let as_string = marker.to_string();
+        let (edition_mark_pda, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata_mint.as_ref(),
+                EDITION.as_bytes(),
+                as_string.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new(edition_mark_pda, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::ReserveEditions(ReserveEditionsArgs {
+            start,
+            end,
+            recipient,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Mint a reserved edition from a master edition, printed to recipient_token_account which must be owned
+/// by the wallet the edition was reserved for
+#[allow(clippy::too_many_arguments)]
+pub fn mint_reserved_edition_from_master_edition_via_token(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_account_owner: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    recipient_token_account: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let mut instruction = mint_new_edition_from_master_edition_via_token(
+        program_id,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint,
+        new_mint_authority,
+        payer,
+        token_account_owner,
+        token_account,
+        new_metadata_update_authority,
+        metadata,
+        metadata_mint,
+        edition,
+    );
+    instruction.accounts.extend([
+        AccountMeta::new(
+            find_edition_reservation_address(&program_id, &metadata_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(recipient_token_account, false),
+    ]);
+    instruction.data = MetadataInstruction::MintReservedEditionFromMasterEditionViaToken(
+        MintNewEditionFromMasterEditionViaTokenArgs { edition },
+    )
+    .try_to_vec()
+    .unwrap();
+
+    instruction
+}
+
+/// Mint consecutive new editions from a master edition, starting at first_edition, to brand new mints
+#[allow(clippy::too_many_arguments)]
+pub fn mint_new_editions_from_master_edition_via_token(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    new_mints: &[Pubkey],
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_account_owner: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    first_edition: u64,
+) -> Instruction {
+    let count = new_mints.len() as u64;
+    let mut accounts = vec![
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(new_mint_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(token_account_owner, true),
+        AccountMeta::new_readonly(token_account, false),
+        AccountMeta::new_readonly(new_metadata_update_authority, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let last_edition = first_edition + count.saturating_sub(1);
+    for marker in first_edition / EDITION_MARKER_BIT_SIZE..=last_edition / EDITION_MARKER_BIT_SIZE {
+        let as_string = marker.to_string();
+        let (edition_mark_pda, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata_mint.as_ref(),
+                EDITION.as_bytes(),
+                as_string.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new(edition_mark_pda, false));
+    }
+
+    for new_mint in new_mints {
+        let (new_metadata, _) = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), program_id.as_ref(), new_mint.as_ref()],
+            &program_id,
+        );
+        let (new_edition, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                new_mint.as_ref(),
+                EDITION.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new(new_metadata, false));
+        accounts.push(AccountMeta::new(new_edition, false));
+        accounts.push(AccountMeta::new(*new_mint, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MintNewEditionsFromMasterEditionViaToken(
+            MintNewEditionsFromMasterEditionViaTokenArgs {
+                first_edition,
+                count,
+            },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Derives the legacy printing record pda for a migrated master edition's mint
+pub fn find_legacy_printing_record_address(program_id: &Pubkey, master_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            LEGACY_PRINTING.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Migrate a Master Edition V1 to V2, keeping its printed editions, reservations and Printing tokens
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_master_edition_v1_to_v2(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    supply: u64,
+    reservation_lists: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(
+            find_legacy_printing_record_address(&program_id, &metadata_mint),
+            false,
+        ),
+        AccountMeta::new(
+            find_edition_reservation_address(&program_id, &metadata_mint),
+            false,
+        ),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    if supply > 0 {
+        for marker in 0..=supply / EDITION_MARKER_BIT_SIZE {
+            let as_string = marker.to_string();
+            let (edition_mark_pda, _) = Pubkey::find_program_address(
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    metadata_mint.as_ref(),
+                    EDITION.as_bytes(),
+                    as_string.as_bytes(),
+                ],
+                &program_id,
+            );
+            accounts.push(AccountMeta::new(edition_mark_pda, false));
+        }
+    }
+
+    for reservation_list in reservation_lists {
+        accounts.push(AccountMeta::new_readonly(*reservation_list, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MigrateMasterEditionV1ToV2
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Mint a new edition from a migrated master edition by burning one of its Printing tokens
+#[allow(clippy::too_many_arguments)]
+pub fn mint_new_edition_from_master_edition_via_legacy_printing_token(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    printing_token_account_owner: Pubkey,
+    printing_token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    printing_mint: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let mut instruction = mint_new_edition_from_master_edition_via_token(
+        program_id,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint,
+        new_mint_authority,
+        payer,
+        printing_token_account_owner,
+        printing_token_account,
+        new_metadata_update_authority,
+        metadata,
+        metadata_mint,
+        edition,
+    );
+    instruction.accounts[8].is_writable = true;
+    instruction.accounts.extend([
+        AccountMeta::new(printing_mint, false),
+        AccountMeta::new_readonly(
+            find_legacy_printing_record_address(&program_id, &metadata_mint),
+            false,
+        ),
+    ]);
+    instruction.data = MetadataInstruction::MintNewEditionFromMasterEditionViaLegacyPrintingToken(
+        MintNewEditionFromMasterEditionViaTokenArgs { edition },
+    )
+    .try_to_vec()
+    .unwrap();
+
+    instruction
+}
+
+/// Set or clear the name and uri template for prints of a master edition
+pub fn set_edition_template(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    master_edition: Pubkey,
+    edition_template: Option<EditionTemplate>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(master_edition, false),
+        ],
+        data: MetadataInstruction::SetEditionTemplate(SetEditionTemplateArgs { edition_template })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Address an allowed transfer program signs with to move royalty enforced tokens
+pub fn find_transfer_authority_address(transfer_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TRANSFER_AUTHORITY.as_bytes()], transfer_program).0
+}
+
+/// Set or clear the programs sales of a royalty enforced token have to go through
+pub fn set_royalty_enforcement(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+    token_account: Pubkey,
+    allowed_transfer_programs: Option<Vec<Pubkey>>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::SetRoyaltyEnforcement(SetRoyaltyEnforcementArgs {
+            allowed_transfer_programs,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Freeze a token account of a royalty enforced mint
+pub fn freeze_enforced_token(
+    program_id: Pubkey,
+    token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::FreezeEnforcedToken
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Thaw a token account of a mint that no longer enforces royalties, as its owner
+pub fn thaw_enforced_token(
+    program_id: Pubkey,
+    token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+    owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(owner, true),
+        ],
+        data: MetadataInstruction::ThawEnforcedToken.try_to_vec().unwrap(),
+    }
+}
+
+/// Move a royalty enforced token as the transfer authority of an allowed program, leaving it
+/// frozen in the destination
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_enforced_token(
+    program_id: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    edition: Pubkey,
+    transfer_program: Pubkey,
+    owner: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(source, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(edition, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(find_transfer_authority_address(&transfer_program), true),
+        AccountMeta::new_readonly(transfer_program, false),
+    ];
+
+    if let Some(owner) = owner {
+        accounts.push(AccountMeta::new_readonly(owner, true));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::TransferEnforcedToken
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CreateMetadataAccountV3 instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v3(
+    program_id: Pubkey,
+    metadata_account: Pubkey,
+    mint: Pubkey,
+    mint_authority: Pubkey,
+    payer: Pubkey,
+    update_authority: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    creators: Option<Vec<Creator>>,
+    seller_fee_basis_points: u16,
+    update_authority_is_signer: bool,
+    is_mutable: bool,
+    collection: Option<Collection>,
+    uses: Option<Uses>,
+    content_hash: Option<[u8; 32]>,
+    token_standard: TokenStandard,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_account, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(mint_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(update_authority, update_authority_is_signer),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::CreateMetadataAccountV3(CreateMetadataAccountArgsV3 {
+            data: DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points,
+                creators,
+                collection,
+                uses,
+                content_hash,
+            },
+            is_mutable,
+            token_standard,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates a CloseEditionAccounts instruction
+pub fn close_edition_accounts(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    metadata_mint: Pubkey,
+    edition_marker_numbers: Vec<u64>,
+    reservation_lists: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(master_edition, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(update_authority, true),
+    ];
+
+    for marker in &edition_marker_numbers {
+        let as_string = marker.to_string();
+        let (edition_mark_pda, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata_mint.as_ref(),
+                EDITION.as_bytes(),
+                as_string.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new(edition_mark_pda, false));
+    }
+
+    for reservation_list in reservation_lists {
+        accounts.push(AccountMeta::new(*reservation_list, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::CloseEditionAccounts(CloseEditionAccountsArgs {
+            edition_marker_numbers,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates a PatchMetadataAccount instruction
+#[allow(clippy::too_many_arguments)]
+pub fn patch_metadata_account(
+    program_id: Pubkey,
+    metadata_account: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+    seller_fee_basis_points: Option<u16>,
+    creators: Option<Vec<Creator>>,
+    removed_creators: Option<Vec<Pubkey>>,
+    is_mutable: Option<bool>,
+    new_update_authority: Option<Pubkey>,
+    payer: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata_account, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+
+    if new_update_authority.is_some() {
+        accounts.push(AccountMeta::new(
+            find_pending_update_authority_address(&program_id, &mint),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(sysvar::rent::id(), false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::PatchMetadataAccount(PatchMetadataAccountArgs {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+            removed_creators,
+            is_mutable,
+            update_authority: new_update_authority,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
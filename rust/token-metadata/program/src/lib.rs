@@ -2,6 +2,7 @@
 
 pub mod deprecated_instruction;
 pub mod deprecated_processor;
+pub mod deser;
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
@@ -8,20 +8,41 @@ use {
             process_deprecated_set_reservation_list,
         },
         error::MetadataError,
-        instruction::MetadataInstruction,
+        instruction::{
+            ApprovePrintDelegateArgs, ApproveUseAuthorityArgs, CloseEditionAccountsArgs,
+            FreezeMetadataArgs, MetadataInstruction, MintNewEditionsFromMasterEditionViaTokenArgs,
+            PatchMetadataAccountArgs, RemoveUnverifiedCreatorsArgs, ReserveEditionsArgs,
+            SetEditionTemplateArgs, SetRoyaltyEnforcementArgs, UtilizeArgs,
+        },
         state::{
-            Data, Key, MasterEditionV1, MasterEditionV2, Metadata, EDITION, MAX_MASTER_EDITION_LEN,
-            PREFIX,
+            get_master_edition, get_reservation_list, Data, DataV2, Edition, EditionMarker,
+            EditionReservation, Key, LegacyPrintingRecord, MasterEditionV1, MasterEditionV2,
+            Metadata, PendingUpdateAuthority, PrintDelegateRecord, ReservedEditionRange,
+            SaleAuthorityRecord, TokenStandard, UseAuthorityRecord, UseMethod, BURN, EDITION,
+            EDITION_MARKER_BIT_SIZE, EDITION_RESERVATION, LEGACY_PRINTING,
+            MAX_ALLOWED_TRANSFER_PROGRAMS, MAX_EDITION_RESERVATION_LEN,
+            MAX_LEGACY_PRINTING_RECORD_LEN, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN,
+            MAX_PRINT_DELEGATE_RECORD_LEN, MAX_SALE_AUTHORITY_RECORD_LEN,
+            MAX_USE_AUTHORITY_RECORD_LEN, PREFIX, PRINT_DELEGATE, SALE_AUTHORITY,
+            TRANSFER_AUTHORITY, USER,
         },
         utils::{
-            assert_data_valid, assert_derivation, assert_initialized,
-            assert_mint_authority_matches_mint, assert_owned_by, assert_signer,
+            assert_collection_update_is_valid, assert_collection_verify_is_valid,
+            assert_data_valid, assert_derivation, assert_edition_template_valid,
+            assert_edition_valid, assert_freeze_authority_is_edition, assert_holds_asset,
+            assert_initialized, assert_mint_authority_matches_mint, assert_owned_by, assert_signer,
             assert_token_program_matches_package, assert_update_authority_is_correct,
-            create_or_allocate_account_raw, get_owner_from_token_account,
+            assert_valid_use, calculate_supply_change, close_account_raw, create_limited_edition,
+            create_or_allocate_account_raw, get_mint_supply, get_owner_from_token_account,
+            get_supply_off_master_edition, load_or_create_edition_marker,
+            mint_edition_from_master_edition_v2, patch_creators,
             process_create_metadata_accounts_logic,
-            process_mint_new_edition_from_master_edition_via_token_logic, puff_out_data_fields,
-            transfer_mint_authority, CreateMetadataAccountsLogicArgs,
-            MintNewEditionFromMasterEditionViaTokenLogicArgs,
+            process_mint_new_edition_from_master_edition_via_token_logic,
+            process_propose_update_authority_logic, puff_out_data_fields,
+            remove_unverified_creators, save_metadata_account, set_token_account_frozen,
+            spl_token_burn, transfer_mint_authority, CreateMetadataAccountsLogicArgs,
+            MintNewEditionFromMasterEditionViaTokenLogicArgs, ProposeUpdateAuthorityLogicArgs,
+            TokenBurnParams,
         },
     },
     arrayref::array_ref,
@@ -30,6 +51,7 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         msg,
+        program::invoke,
         program_error::ProgramError,
         pubkey::Pubkey,
     },
@@ -46,12 +68,33 @@ pub fn process_instruction<'a>(
     match instruction {
         MetadataInstruction::CreateMetadataAccount(args) => {
             msg!("Instruction: Create Metadata Accounts");
+            process_create_metadata_accounts(
+                program_id,
+                accounts,
+                DataV2 {
+                    name: args.data.name,
+                    symbol: args.data.symbol,
+                    uri: args.data.uri,
+                    seller_fee_basis_points: args.data.seller_fee_basis_points,
+                    creators: args.data.creators,
+                    collection: None,
+                    uses: None,
+                    content_hash: None,
+                },
+                false,
+                args.is_mutable,
+                None,
+            )
+        }
+        MetadataInstruction::CreateMetadataAccountV2(args) => {
+            msg!("Instruction: Create Metadata Accounts v2");
             process_create_metadata_accounts(
                 program_id,
                 accounts,
                 args.data,
                 false,
                 args.is_mutable,
+                None,
             )
         }
         MetadataInstruction::UpdateMetadataAccount(args) => {
@@ -64,6 +107,16 @@ pub fn process_instruction<'a>(
                 args.primary_sale_happened,
             )
         }
+        MetadataInstruction::UpdateMetadataAccountV2(args) => {
+            msg!("Instruction: Update Metadata Accounts v2");
+            process_update_metadata_accounts_v2(
+                program_id,
+                accounts,
+                args.data,
+                args.update_authority,
+                args.primary_sale_happened,
+            )
+        }
         MetadataInstruction::DeprecatedCreateMasterEdition(args) => {
             msg!("Instruction: Deprecated Create Master Edition");
             process_deprecated_create_master_edition(program_id, accounts, args.max_supply)
@@ -134,15 +187,155 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Puff Metadata");
             process_puff_metadata_account(program_id, accounts)
         }
+        MetadataInstruction::VerifyCollection => {
+            msg!("Instruction: Verify Collection");
+            process_verify_collection(program_id, accounts)
+        }
+        MetadataInstruction::UnverifyCollection => {
+            msg!("Instruction: Unverify Collection");
+            process_unverify_collection(program_id, accounts)
+        }
+        MetadataInstruction::ProposeUpdateAuthority => {
+            msg!("Instruction: Propose Update Authority");
+            process_propose_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::AcceptUpdateAuthority => {
+            msg!("Instruction: Accept Update Authority");
+            process_accept_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::CancelUpdateAuthority => {
+            msg!("Instruction: Cancel Update Authority");
+            process_cancel_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::BurnNft => {
+            msg!("Instruction: Burn NFT");
+            process_burn_nft(program_id, accounts)
+        }
+        MetadataInstruction::ApproveUseAuthority(args) => {
+            msg!("Instruction: Approve Use Authority");
+            process_approve_use_authority(program_id, accounts, args)
+        }
+        MetadataInstruction::RevokeUseAuthority => {
+            msg!("Instruction: Revoke Use Authority");
+            process_revoke_use_authority(program_id, accounts)
+        }
+        MetadataInstruction::Utilize(args) => {
+            msg!("Instruction: Utilize");
+            process_utilize(program_id, accounts, args)
+        }
+        MetadataInstruction::ApproveSaleAuthority => {
+            msg!("Instruction: Approve Sale Authority");
+            process_approve_sale_authority(program_id, accounts)
+        }
+        MetadataInstruction::RevokeSaleAuthority => {
+            msg!("Instruction: Revoke Sale Authority");
+            process_revoke_sale_authority(program_id, accounts)
+        }
+        MetadataInstruction::UpdatePrimarySaleHappenedViaAuthority => {
+            msg!("Instruction: Update primary sale via authority");
+            process_update_primary_sale_happened_via_authority(program_id, accounts)
+        }
+        MetadataInstruction::FreezeMetadata(args) => {
+            msg!("Instruction: Freeze Metadata");
+            process_freeze_metadata(program_id, accounts, args)
+        }
+        MetadataInstruction::SignMetadataBatch => {
+            msg!("Instruction: Sign Metadata Batch");
+            process_sign_metadata_batch(program_id, accounts)
+        }
+        MetadataInstruction::RemoveCreatorVerification => {
+            msg!("Instruction: Remove Creator Verification");
+            process_remove_creator_verification(program_id, accounts)
+        }
+        MetadataInstruction::RemoveUnverifiedCreators(args) => {
+            msg!("Instruction: Remove Unverified Creators");
+            process_remove_unverified_creators(program_id, accounts, args)
+        }
+        MetadataInstruction::ApprovePrintDelegate(args) => {
+            msg!("Instruction: Approve Print Delegate");
+            process_approve_print_delegate(program_id, accounts, args)
+        }
+        MetadataInstruction::RevokePrintDelegate => {
+            msg!("Instruction: Revoke Print Delegate");
+            process_revoke_print_delegate(program_id, accounts)
+        }
+        MetadataInstruction::ReserveEditions(args) => {
+            msg!("Instruction: Reserve Editions");
+            process_reserve_editions(program_id, accounts, args)
+        }
+        MetadataInstruction::MintNewEditionsFromMasterEditionViaToken(args) => {
+            msg!("Instruction: Mint New Editions from Master Edition Via Token");
+            process_mint_new_editions_from_master_edition_via_token(program_id, accounts, args)
+        }
+        MetadataInstruction::MigrateMasterEditionV1ToV2 => {
+            msg!("Instruction: Migrate Master Edition V1 to V2");
+            process_migrate_master_edition_v1_to_v2(program_id, accounts)
+        }
+        MetadataInstruction::MintNewEditionFromMasterEditionViaLegacyPrintingToken(args) => {
+            msg!("Instruction: Mint New Edition from Master Edition Via Legacy Printing Token");
+            process_mint_new_edition_from_master_edition_via_legacy_printing_token(
+                program_id,
+                accounts,
+                args.edition,
+            )
+        }
+        MetadataInstruction::SetEditionTemplate(args) => {
+            msg!("Instruction: Set Edition Template");
+            process_set_edition_template(program_id, accounts, args)
+        }
+        MetadataInstruction::SetRoyaltyEnforcement(args) => {
+            msg!("Instruction: Set Royalty Enforcement");
+            process_set_royalty_enforcement(program_id, accounts, args)
+        }
+        MetadataInstruction::FreezeEnforcedToken => {
+            msg!("Instruction: Freeze Enforced Token");
+            process_freeze_enforced_token(program_id, accounts)
+        }
+        MetadataInstruction::ThawEnforcedToken => {
+            msg!("Instruction: Thaw Enforced Token");
+            process_thaw_enforced_token(program_id, accounts)
+        }
+        MetadataInstruction::CreateMetadataAccountV3(args) => {
+            msg!("Instruction: Create Metadata Accounts v3");
+            process_create_metadata_accounts(
+                program_id,
+                accounts,
+                args.data,
+                false,
+                args.is_mutable,
+                Some(args.token_standard),
+            )
+        }
+        MetadataInstruction::CloseEditionAccounts(args) => {
+            msg!("Instruction: Close Edition Accounts");
+            process_close_edition_accounts(program_id, accounts, args)
+        }
+        MetadataInstruction::PatchMetadataAccount(args) => {
+            msg!("Instruction: Patch Metadata Account");
+            process_patch_metadata_account(program_id, accounts, args)
+        }
+        MetadataInstruction::MintReservedEditionFromMasterEditionViaToken(args) => {
+            msg!("Instruction: Mint Reserved Edition from Master Edition Via Token");
+            process_mint_reserved_edition_from_master_edition_via_token(
+                program_id,
+                accounts,
+                args.edition,
+            )
+        }
+        MetadataInstruction::TransferEnforcedToken => {
+            msg!("Instruction: Transfer Enforced Token");
+            process_transfer_enforced_token(program_id, accounts)
+        }
     }
 }
 
 pub fn process_create_metadata_accounts<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
-    data: Data,
+    data: DataV2,
     allow_direct_creator_writes: bool,
     is_mutable: bool,
+    token_standard: Option<TokenStandard>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let metadata_account_info = next_account_info(account_info_iter)?;
@@ -167,6 +360,7 @@ pub fn process_create_metadata_accounts<'a>(
         data,
         allow_direct_creator_writes,
         is_mutable,
+        token_standard,
     )
 }
 
@@ -215,9 +409,66 @@ pub fn process_update_metadata_accounts(
         }
     }
 
-    puff_out_data_fields(&mut metadata);
+    save_metadata_account(&metadata, metadata_account_info)?;
+    Ok(())
+}
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+/// Update existing account instruction, v2 with collection support
+pub fn process_update_metadata_accounts_v2(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    optional_data: Option<DataV2>,
+    update_authority: Option<Pubkey>,
+    primary_sale_happened: Option<bool>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+
+    assert_owned_by(metadata_account_info, program_id)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if let Some(data) = optional_data {
+        if metadata.is_mutable {
+            assert_data_valid(
+                &data.to_v1(),
+                update_authority_info.key,
+                &metadata,
+                false,
+                update_authority_info.is_signer,
+                true,
+            )?;
+            assert_collection_update_is_valid(&metadata.collection, &data.collection)?;
+            assert_valid_use(&data.uses, &metadata.uses)?;
+            metadata.data = data.to_v1();
+            metadata.collection = data.collection;
+            metadata.uses = data.uses;
+            metadata.content_hash = data.content_hash;
+        } else {
+            return Err(MetadataError::DataIsImmutable.into());
+        }
+    }
+
+    if let Some(val) = update_authority {
+        let new_update_authority_info = next_account_info(account_info_iter)?;
+        if *new_update_authority_info.key != val {
+            return Err(MetadataError::PendingUpdateAuthorityMismatch.into());
+        }
+        assert_signer(new_update_authority_info)?;
+        metadata.update_authority = val;
+    }
+
+    if let Some(val) = primary_sale_happened {
+        if val {
+            metadata.primary_sale_happened = val
+        } else {
+            return Err(MetadataError::PrimarySaleCanOnlyBeFlippedToTrue.into());
+        }
+    }
+
+    save_metadata_account(&metadata, metadata_account_info)?;
     Ok(())
 }
 
@@ -266,6 +517,36 @@ pub fn process_sign_metadata(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     let creator_info = next_account_info(account_info_iter)?;
 
     assert_signer(creator_info)?;
+    verify_creator(program_id, metadata_info, creator_info)
+}
+
+/// Verify one creator on many metadata accounts. Any failure fails the whole batch.
+pub fn process_sign_metadata_batch(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let creator_info = next_account_info(account_info_iter)?;
+
+    assert_signer(creator_info)?;
+
+    let mut signed = 0;
+    for metadata_info in account_info_iter {
+        verify_creator(program_id, metadata_info, creator_info)?;
+        msg!("Verified creator on {}", metadata_info.key);
+        signed += 1;
+    }
+
+    if signed == 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    Ok(())
+}
+
+fn verify_creator(
+    program_id: &Pubkey,
+    metadata_info: &AccountInfo,
+    creator_info: &AccountInfo,
+) -> ProgramResult {
     assert_owned_by(metadata_info, program_id)?;
 
     let mut metadata = Metadata::from_account_info(metadata_info)?;
@@ -335,6 +616,12 @@ pub fn process_create_master_edition(
         return Err(MetadataError::EditionMintDecimalsShouldBeZero.into());
     }
 
+    if let Some(TokenStandard::Fungible) | Some(TokenStandard::FungibleAsset) =
+        metadata.token_standard
+    {
+        return Err(MetadataError::FungibleCannotBeMasterEdition.into());
+    }
+
     assert_update_authority_is_correct(&metadata, update_authority_info)?;
 
     if mint.supply != 1 {
@@ -401,6 +688,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     let token_program_account_info = next_account_info(account_info_iter)?;
     let system_account_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
+    let print_delegate_record_info = next_account_info(account_info_iter).ok();
 
     process_mint_new_edition_from_master_edition_via_token_logic(
         &program_id,
@@ -419,12 +707,66 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
             token_program_account_info,
             system_account_info,
             rent_info,
+            print_delegate_record_info,
+            edition_reservation_info: None,
+            recipient_token_account_info: None,
         },
         edition,
         ignore_owner_signer,
     )
 }
 
+pub fn process_mint_reserved_edition_from_master_edition_via_token<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    edition: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let new_metadata_account_info = next_account_info(account_info_iter)?;
+    let new_edition_account_info = next_account_info(account_info_iter)?;
+    let master_edition_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_marker_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let owner_account_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_metadata_account_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let edition_reservation_info = next_account_info(account_info_iter)?;
+    let recipient_token_account_info = next_account_info(account_info_iter)?;
+    let print_delegate_record_info = next_account_info(account_info_iter).ok();
+
+    process_mint_new_edition_from_master_edition_via_token_logic(
+        &program_id,
+        MintNewEditionFromMasterEditionViaTokenLogicArgs {
+            new_metadata_account_info,
+            new_edition_account_info,
+            master_edition_account_info,
+            mint_info,
+            edition_marker_info,
+            mint_authority_info,
+            payer_account_info,
+            owner_account_info,
+            token_account_info,
+            update_authority_info,
+            master_metadata_account_info,
+            token_program_account_info,
+            system_account_info,
+            rent_info,
+            print_delegate_record_info,
+            edition_reservation_info: Some(edition_reservation_info),
+            recipient_token_account_info: Some(recipient_token_account_info),
+        },
+        edition,
+        false,
+    )
+}
+
 pub fn process_convert_master_edition_v1_to_v2(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -461,6 +803,8 @@ pub fn process_convert_master_edition_v1_to_v2(
         key: Key::MasterEditionV2,
         supply: master_edition.supply,
         max_supply: master_edition.max_supply,
+        reserved: [0; 64],
+        edition_template: None,
     }
     .serialize(&mut *master_edition_info.data.borrow_mut())?;
 
@@ -577,13 +921,17 @@ pub fn process_mint_new_edition_from_master_edition_via_vault_proxy<'a>(
         token_program_account_info,
         system_account_info,
         rent_info,
+        print_delegate_record_info: None,
+        edition_reservation_info: None,
+        recipient_token_account_info: None,
     };
 
     process_mint_new_edition_from_master_edition_via_token_logic(program_id, args, edition, true)
 }
 
 /// Puff out the variable length fields to a fixed length on a metadata
-/// account in a permissionless way.
+/// account in a permissionless way. Only older, fixed size accounts are
+/// puffed, newer ones keep their fields unpadded.
 pub fn process_puff_metadata_account(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -595,6 +943,10 @@ pub fn process_puff_metadata_account(
 
     assert_owned_by(metadata_account_info, program_id)?;
 
+    if metadata_account_info.data_len() != MAX_METADATA_LEN {
+        return Err(MetadataError::CannotPuffVariableLengthMetadata.into());
+    }
+
     puff_out_data_fields(&mut metadata);
 
     let edition_seeds = &[
@@ -609,3 +961,1824 @@ pub fn process_puff_metadata_account(
     metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
     Ok(())
 }
+
+pub fn process_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let collection_authority_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+    assert_collection_verify_is_valid(
+        program_id,
+        &metadata,
+        collection_authority_info,
+        collection_mint_info,
+        collection_metadata_info,
+        collection_edition_info,
+    )?;
+
+    if let Some(collection) = &mut metadata.collection {
+        if collection.verified {
+            return Err(MetadataError::CollectionAlreadyVerified.into());
+        }
+        collection.verified = true;
+    }
+    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_unverify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let collection_authority_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+    assert_collection_verify_is_valid(
+        program_id,
+        &metadata,
+        collection_authority_info,
+        collection_mint_info,
+        collection_metadata_info,
+        collection_edition_info,
+    )?;
+
+    if let Some(collection) = &mut metadata.collection {
+        if !collection.verified {
+            return Err(MetadataError::CollectionNotVerified.into());
+        }
+        collection.verified = false;
+    }
+    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_propose_update_authority<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pending_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let new_update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    process_propose_update_authority_logic(
+        program_id,
+        ProposeUpdateAuthorityLogicArgs {
+            pending_info,
+            metadata_info,
+            payer_info,
+            system_account_info,
+            rent_info,
+        },
+        &metadata,
+        new_update_authority_info.key,
+    )
+}
+
+pub fn process_accept_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+    let new_update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+
+    assert_signer(new_update_authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(pending_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    let pending = PendingUpdateAuthority::from_account_info(pending_info)?;
+
+    if pending.metadata != *metadata_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityMetadataMismatch.into());
+    }
+
+    if pending.new_update_authority != *new_update_authority_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityMismatch.into());
+    }
+
+    if pending.update_authority != metadata.update_authority {
+        return Err(MetadataError::PendingUpdateAuthorityStale.into());
+    }
+
+    if pending.payer != *payer_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityPayerMismatch.into());
+    }
+
+    metadata.update_authority = pending.new_update_authority;
+    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+
+    close_account_raw(payer_info, pending_info)?;
+
+    Ok(())
+}
+
+pub fn process_cancel_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pending_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(pending_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    let pending = PendingUpdateAuthority::from_account_info(pending_info)?;
+    if pending.metadata != *metadata_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityMetadataMismatch.into());
+    }
+
+    if pending.payer != *payer_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityPayerMismatch.into());
+    }
+
+    close_account_raw(payer_info, pending_info)?;
+
+    Ok(())
+}
+
+/// Burn an NFT, closing its token account, metadata and edition so the owner gets back all of the rent.
+pub fn process_burn_nft<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(edition_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+
+    if metadata.mint != *mint_info.key || token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token_account.owner != *owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token_account.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    assert_edition_valid(program_id, mint_info.key, edition_info)?;
+
+    let edition_key = edition_info.data.borrow()[0];
+    if edition_key == Key::MasterEditionV2 as u8 {
+        let master_edition = MasterEditionV2::from_account_info(edition_info)?;
+        // Prints keep pointing at their master, so only its update authority can let them outlive it.
+        if master_edition.supply > 0 {
+            let update_authority_info = next_account_info(account_info_iter)
+                .map_err(|_| MetadataError::MasterEditionHasPrints)?;
+            assert_update_authority_is_correct(&metadata, update_authority_info)?;
+        }
+    } else if edition_key == Key::EditionV1 as u8 {
+        let edition = Edition::from_account_info(edition_info)?;
+        let master_edition_info = next_account_info(account_info_iter)?;
+        let master_mint_info = next_account_info(account_info_iter)?;
+        let edition_marker_info = next_account_info(account_info_iter)?;
+
+        if edition.parent != *master_edition_info.key {
+            return Err(MetadataError::MasterRecordMismatch.into());
+        }
+        assert_edition_valid(program_id, master_mint_info.key, master_edition_info)?;
+
+        let edition_marker_number = edition
+            .edition
+            .checked_div(EDITION_MARKER_BIT_SIZE)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        assert_derivation(
+            program_id,
+            edition_marker_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                master_mint_info.key.as_ref(),
+                EDITION.as_bytes(),
+                edition_marker_number.to_string().as_bytes(),
+            ],
+        )?;
+
+        // Prints of a Master Edition V1 were never recorded in an edition marker.
+        if !edition_marker_info.data_is_empty() {
+            assert_owned_by(edition_marker_info, program_id)?;
+            let mut edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+            edition_marker.remove_edition(edition.edition)?;
+            edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
+        }
+    } else {
+        // Master Edition V1s still have printing mints tied to them.
+        return Err(MetadataError::InvalidOperation.into());
+    }
+
+    spl_token_burn(TokenBurnParams {
+        mint: mint_info.clone(),
+        source: token_account_info.clone(),
+        amount: token_account.amount,
+        authority: owner_info.clone(),
+        authority_signer_seeds: None,
+        token_program: token_program_info.clone(),
+    })?;
+
+    invoke(
+        &spl_token::instruction::close_account(
+            token_program_info.key,
+            token_account_info.key,
+            owner_info.key,
+            owner_info.key,
+            &[],
+        )?,
+        &[
+            token_account_info.clone(),
+            owner_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
+
+    close_account_raw(owner_info, metadata_info)?;
+    close_account_raw(owner_info, edition_info)?;
+
+    Ok(())
+}
+
+pub fn process_approve_use_authority<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ApproveUseAuthorityArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let use_authority_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let use_authority_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let burner_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_holds_asset(&metadata, token_account_info, owner_info)?;
+
+    let uses = metadata.uses.ok_or(MetadataError::Unusable)?;
+    if args.number_of_uses == 0 || args.number_of_uses > uses.remaining {
+        return Err(MetadataError::NotEnoughUses.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        use_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            USER.as_bytes(),
+            owner_info.key.as_ref(),
+            use_authority_info.key.as_ref(),
+        ],
+    )?;
+
+    if use_authority_record_info.data_is_empty() {
+        let use_authority_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            USER.as_bytes(),
+            owner_info.key.as_ref(),
+            use_authority_info.key.as_ref(),
+            &[bump_seed],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            use_authority_record_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            MAX_USE_AUTHORITY_RECORD_LEN,
+            use_authority_seeds,
+        )?;
+    } else {
+        assert_owned_by(use_authority_record_info, program_id)?;
+    }
+
+    let mut record = UseAuthorityRecord::from_account_info(use_authority_record_info)?;
+    record.key = Key::UseAuthorityRecord;
+    record.allowed_uses = args.number_of_uses;
+    record.bump = bump_seed;
+    record.serialize(&mut *use_authority_record_info.data.borrow_mut())?;
+
+    // The use authority can't sign for the owner, so the burner pda burns the token on its last use.
+    if uses.use_method == UseMethod::Burn {
+        assert_derivation(
+            program_id,
+            burner_info,
+            &[PREFIX.as_bytes(), program_id.as_ref(), BURN.as_bytes()],
+        )?;
+
+        invoke(
+            &spl_token::instruction::approve(
+                token_program_info.key,
+                token_account_info.key,
+                burner_info.key,
+                owner_info.key,
+                &[],
+                1,
+            )?,
+            &[
+                token_account_info.clone(),
+                burner_info.clone(),
+                owner_info.clone(),
+                token_program_info.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn process_revoke_use_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let use_authority_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let use_authority_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(use_authority_record_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_holds_asset(&metadata, token_account_info, owner_info)?;
+
+    assert_derivation(
+        program_id,
+        use_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            USER.as_bytes(),
+            owner_info.key.as_ref(),
+            use_authority_info.key.as_ref(),
+        ],
+    )?;
+
+    close_account_raw(owner_info, use_authority_record_info)?;
+
+    if let Some(uses) = metadata.uses {
+        if uses.use_method == UseMethod::Burn {
+            invoke(
+                &spl_token::instruction::revoke(
+                    token_program_info.key,
+                    token_account_info.key,
+                    owner_info.key,
+                    &[],
+                )?,
+                &[
+                    token_account_info.clone(),
+                    owner_info.clone(),
+                    token_program_info.clone(),
+                ],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Spend uses on an asset, burning the token when the last use of a Burn asset is spent.
+pub fn process_utilize<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UtilizeArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let use_authority_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(use_authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    assert_holds_asset(&metadata, token_account_info, owner_info)?;
+
+    let mut uses = metadata.uses.clone().ok_or(MetadataError::Unusable)?;
+    if args.number_of_uses == 0 || args.number_of_uses > uses.remaining {
+        return Err(MetadataError::NotEnoughUses.into());
+    }
+
+    let mut burner_info = None;
+    if use_authority_info.key != owner_info.key {
+        let use_authority_record_info = next_account_info(account_info_iter)?;
+        assert_owned_by(use_authority_record_info, program_id)?;
+        assert_derivation(
+            program_id,
+            use_authority_record_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata.mint.as_ref(),
+                USER.as_bytes(),
+                owner_info.key.as_ref(),
+                use_authority_info.key.as_ref(),
+            ],
+        )?;
+
+        let mut record = UseAuthorityRecord::from_account_info(use_authority_record_info)?;
+        if record.key != Key::UseAuthorityRecord {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+        if args.number_of_uses > record.allowed_uses {
+            return Err(MetadataError::NotEnoughUses.into());
+        }
+        record.allowed_uses -= args.number_of_uses;
+        record.serialize(&mut *use_authority_record_info.data.borrow_mut())?;
+
+        burner_info = Some(next_account_info(account_info_iter)?);
+    }
+
+    uses.remaining -= args.number_of_uses;
+    let burn = uses.remaining == 0 && uses.use_method == UseMethod::Burn;
+    metadata.uses = Some(uses);
+    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+
+    if burn {
+        match burner_info {
+            None => spl_token_burn(TokenBurnParams {
+                mint: mint_info.clone(),
+                source: token_account_info.clone(),
+                amount: 1,
+                authority: owner_info.clone(),
+                authority_signer_seeds: None,
+                token_program: token_program_info.clone(),
+            })?,
+            Some(burner_info) => {
+                let bump_seed = assert_derivation(
+                    program_id,
+                    burner_info,
+                    &[PREFIX.as_bytes(), program_id.as_ref(), BURN.as_bytes()],
+                )?;
+                let burner_seeds = &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    BURN.as_bytes(),
+                    &[bump_seed],
+                ];
+
+                spl_token_burn(TokenBurnParams {
+                    mint: mint_info.clone(),
+                    source: token_account_info.clone(),
+                    amount: 1,
+                    authority: burner_info.clone(),
+                    authority_signer_seeds: Some(burner_seeds),
+                    token_program: token_program_info.clone(),
+                })?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn process_approve_sale_authority<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let sale_authority_record_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let sale_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    let bump_seed = assert_derivation(
+        program_id,
+        sale_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            SALE_AUTHORITY.as_bytes(),
+            sale_authority_info.key.as_ref(),
+        ],
+    )?;
+
+    // Approving an already approved sale authority is a no-op.
+    if !sale_authority_record_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let sale_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata.mint.as_ref(),
+        SALE_AUTHORITY.as_bytes(),
+        sale_authority_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        sale_authority_record_info,
+        rent_info,
+        system_account_info,
+        payer_info,
+        MAX_SALE_AUTHORITY_RECORD_LEN,
+        sale_authority_seeds,
+    )?;
+
+    let record = SaleAuthorityRecord {
+        key: Key::SaleAuthorityRecord,
+        bump: bump_seed,
+    };
+    record.serialize(&mut *sale_authority_record_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_revoke_sale_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let sale_authority_record_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let sale_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(sale_authority_record_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    assert_derivation(
+        program_id,
+        sale_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            SALE_AUTHORITY.as_bytes(),
+            sale_authority_info.key.as_ref(),
+        ],
+    )?;
+
+    close_account_raw(update_authority_info, sale_authority_record_info)?;
+
+    Ok(())
+}
+
+pub fn process_update_primary_sale_happened_via_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_signer(authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+    if metadata.update_authority != *authority_info.key {
+        let sale_authority_record_info = next_account_info(account_info_iter)
+            .map_err(|_| MetadataError::InvalidSaleAuthority)?;
+        assert_derivation(
+            program_id,
+            sale_authority_record_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata.mint.as_ref(),
+                SALE_AUTHORITY.as_bytes(),
+                authority_info.key.as_ref(),
+            ],
+        )?;
+
+        // Revoking zeroes the record and drains its lamports, but earlier in the same transaction
+        // it is still owned by this program, so check that it is funded and initialized too.
+        if sale_authority_record_info.owner != program_id
+            || sale_authority_record_info.lamports() == 0
+            || sale_authority_record_info.data_is_empty()
+        {
+            return Err(MetadataError::InvalidSaleAuthority.into());
+        }
+        let record = SaleAuthorityRecord::from_account_info(sale_authority_record_info)?;
+        if record.key != Key::SaleAuthorityRecord {
+            return Err(MetadataError::InvalidSaleAuthority.into());
+        }
+    }
+
+    metadata.primary_sale_happened = true;
+    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Lock a metadata for good, recording the hash of its content if given.
+pub fn process_freeze_metadata<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: FreezeMetadataArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_account_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    if args.content_hash.is_some() {
+        metadata.content_hash = args.content_hash;
+    }
+    metadata.is_mutable = false;
+
+    save_metadata_account(&metadata, metadata_account_info)?;
+    Ok(())
+}
+
+pub fn process_remove_creator_verification(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+
+    assert_signer(creator_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+    match &mut metadata.data.creators {
+        Some(creators) => match creators.iter_mut().find(|c| c.address == *creator_info.key) {
+            Some(creator) => creator.verified = false,
+            None => return Err(MetadataError::CreatorNotFound.into()),
+        },
+        None => return Err(MetadataError::NoCreatorsPresentOnMetadata.into()),
+    }
+    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_remove_unverified_creators<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RemoveUnverifiedCreatorsArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    let creators = metadata
+        .data
+        .creators
+        .as_ref()
+        .ok_or(MetadataError::NoCreatorsPresentOnMetadata)?;
+
+    let mut data = metadata.data.clone();
+    data.creators = remove_unverified_creators(creators, &args.creators)?;
+    assert_data_valid(
+        &data,
+        update_authority_info.key,
+        &metadata,
+        false,
+        update_authority_info.is_signer,
+        true,
+    )?;
+    metadata.data = data;
+
+    save_metadata_account(&metadata, metadata_info)?;
+
+    Ok(())
+}
+
+pub fn process_approve_print_delegate<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ApprovePrintDelegateArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let print_delegate_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let print_delegate_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_signer(payer_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_holds_asset(&metadata, token_account_info, owner_info)?;
+
+    let bump_seed = assert_derivation(
+        program_id,
+        print_delegate_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PRINT_DELEGATE.as_bytes(),
+            print_delegate_info.key.as_ref(),
+        ],
+    )?;
+
+    if print_delegate_record_info.data_is_empty() {
+        let print_delegate_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PRINT_DELEGATE.as_bytes(),
+            print_delegate_info.key.as_ref(),
+            &[bump_seed],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            print_delegate_record_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            MAX_PRINT_DELEGATE_RECORD_LEN,
+            print_delegate_seeds,
+        )?;
+    } else {
+        assert_owned_by(print_delegate_record_info, program_id)?;
+    }
+
+    let record = PrintDelegateRecord {
+        key: Key::PrintDelegateRecord,
+        holder: *owner_info.key,
+        remaining_prints: args.max_prints,
+        expiry: args.expiry,
+        bump: bump_seed,
+    };
+    record.serialize(&mut *print_delegate_record_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_revoke_print_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let print_delegate_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let print_delegate_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(print_delegate_record_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_derivation(
+        program_id,
+        print_delegate_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PRINT_DELEGATE.as_bytes(),
+            print_delegate_info.key.as_ref(),
+        ],
+    )?;
+
+    let record = PrintDelegateRecord::from_account_info(print_delegate_record_info)?;
+    if record.holder != *owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    close_account_raw(owner_info, print_delegate_record_info)?;
+
+    Ok(())
+}
+
+pub fn process_reserve_editions<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ReserveEditionsArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let edition_reservation_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(master_edition_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_edition_valid(program_id, &metadata.mint, master_edition_info)?;
+    let master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+
+    if args.start == 0
+        || args.start > args.end
+        || master_edition
+            .max_supply
+            .map_or(false, |max_supply| args.end > max_supply)
+    {
+        return Err(MetadataError::InvalidEditionReservation.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        edition_reservation_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION_RESERVATION.as_bytes(),
+        ],
+    )?;
+
+    // Mark every reserved edition as taken, so it can't be printed without the reservation.
+    for marker_number in args.start / EDITION_MARKER_BIT_SIZE..=args.end / EDITION_MARKER_BIT_SIZE {
+        let edition_marker_info = next_account_info(account_info_iter)?;
+        let mut edition_marker = load_or_create_edition_marker(
+            program_id,
+            edition_marker_info,
+            &metadata.mint,
+            marker_number,
+            payer_info,
+            system_account_info,
+            rent_info,
+        )?;
+
+        let first = args.start.max(marker_number * EDITION_MARKER_BIT_SIZE);
+        let last = args.end.min(
+            (marker_number * EDITION_MARKER_BIT_SIZE).saturating_add(EDITION_MARKER_BIT_SIZE - 1),
+        );
+        for edition in first..=last {
+            if edition_marker.edition_taken(edition)? {
+                return Err(MetadataError::EditionAlreadyReserved.into());
+            }
+            edition_marker.insert_edition(edition)?;
+        }
+        edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
+    }
+
+    let range = ReservedEditionRange::new(args.start, args.end, args.recipient)?;
+
+    let reservation = if edition_reservation_info.data_is_empty() {
+        let reservation = EditionReservation {
+            key: Key::EditionReservation,
+            bump: bump_seed,
+            ranges: vec![range],
+        };
+        let edition_reservation_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION_RESERVATION.as_bytes(),
+            &[bump_seed],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            edition_reservation_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            MAX_EDITION_RESERVATION_LEN,
+            edition_reservation_seeds,
+        )?;
+
+        reservation
+    } else {
+        assert_owned_by(edition_reservation_info, program_id)?;
+        let mut reservation = EditionReservation::from_account_info(edition_reservation_info)?;
+        reservation.ranges.push(range);
+        reservation
+    };
+
+    if reservation.try_to_vec()?.len() > edition_reservation_info.data_len() {
+        return Err(MetadataError::EditionReservationFull.into());
+    }
+    reservation.serialize(&mut *edition_reservation_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_mint_new_editions_from_master_edition_via_token<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: MintNewEditionsFromMasterEditionViaTokenArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let master_edition_account_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let owner_account_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_metadata_account_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_token_program_matches_package(token_program_account_info)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_owned_by(master_edition_account_info, program_id)?;
+    assert_owned_by(master_metadata_account_info, program_id)?;
+    assert_signer(owner_account_info)?;
+
+    let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+
+    if token_account.owner != *owner_account_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token_account.mint != master_metadata.mint {
+        return Err(MetadataError::TokenAccountMintMismatchV2.into());
+    }
+
+    if token_account.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    assert_edition_valid(
+        program_id,
+        &master_metadata.mint,
+        master_edition_account_info,
+    )?;
+
+    if args.count == 0 {
+        return Err(MetadataError::InvalidOperation.into());
+    }
+    let last_edition = args
+        .first_edition
+        .checked_add(args.count - 1)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
+    for marker_number in
+        args.first_edition / EDITION_MARKER_BIT_SIZE..=last_edition / EDITION_MARKER_BIT_SIZE
+    {
+        let edition_marker_info = next_account_info(account_info_iter)?;
+        let mut edition_marker = load_or_create_edition_marker(
+            program_id,
+            edition_marker_info,
+            &master_metadata.mint,
+            marker_number,
+            payer_account_info,
+            system_account_info,
+            rent_info,
+        )?;
+
+        let first = args
+            .first_edition
+            .max(marker_number * EDITION_MARKER_BIT_SIZE);
+        let last = last_edition.min(
+            (marker_number * EDITION_MARKER_BIT_SIZE).saturating_add(EDITION_MARKER_BIT_SIZE - 1),
+        );
+        for edition in first..=last {
+            if edition_marker.edition_taken(edition)? {
+                return Err(MetadataError::AlreadyInitialized.into());
+            }
+            edition_marker.insert_edition(edition)?;
+        }
+        edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
+    }
+
+    let me_supply = get_supply_off_master_edition(master_edition_account_info)?;
+    calculate_supply_change(
+        master_edition_account_info,
+        None,
+        Some(last_edition),
+        me_supply,
+    )?;
+
+    for edition in args.first_edition..=last_edition {
+        let new_metadata_account_info = next_account_info(account_info_iter)?;
+        let new_edition_account_info = next_account_info(account_info_iter)?;
+        let mint_info = next_account_info(account_info_iter)?;
+
+        assert_owned_by(mint_info, &spl_token::id())?;
+        if !new_metadata_account_info.data_is_empty() || !new_edition_account_info.data_is_empty() {
+            return Err(MetadataError::AlreadyInitialized.into());
+        }
+
+        create_limited_edition(
+            program_id,
+            master_metadata.clone(),
+            new_metadata_account_info,
+            new_edition_account_info,
+            master_edition_account_info,
+            mint_info,
+            mint_authority_info,
+            payer_account_info,
+            update_authority_info,
+            token_program_account_info,
+            system_account_info,
+            rent_info,
+            edition,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn process_migrate_master_edition_v1_to_v2<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let legacy_printing_record_info = next_account_info(account_info_iter)?;
+    let edition_reservation_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(master_edition_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_edition_valid(program_id, &metadata.mint, master_edition_info)?;
+    let master_edition = MasterEditionV1::from_account_info(master_edition_info)?;
+
+    // Wipe the V1 fields first so nothing of them is left behind the shorter V2 layout.
+    for byte in master_edition_info.data.borrow_mut().iter_mut() {
+        *byte = 0;
+    }
+    MasterEditionV2 {
+        key: Key::MasterEditionV2,
+        supply: master_edition.supply,
+        max_supply: master_edition.max_supply,
+        reserved: [0; 64],
+        edition_template: None,
+    }
+    .serialize(&mut *master_edition_info.data.borrow_mut())?;
+
+    let legacy_printing_bump = assert_derivation(
+        program_id,
+        legacy_printing_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            LEGACY_PRINTING.as_bytes(),
+        ],
+    )?;
+    let legacy_printing_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata.mint.as_ref(),
+        LEGACY_PRINTING.as_bytes(),
+        &[legacy_printing_bump],
+    ];
+    create_or_allocate_account_raw(
+        *program_id,
+        legacy_printing_record_info,
+        rent_info,
+        system_account_info,
+        payer_info,
+        MAX_LEGACY_PRINTING_RECORD_LEN,
+        legacy_printing_seeds,
+    )?;
+    LegacyPrintingRecord {
+        key: Key::LegacyPrintingRecord,
+        printing_mint: master_edition.printing_mint,
+        bump: legacy_printing_bump,
+    }
+    .serialize(&mut *legacy_printing_record_info.data.borrow_mut())?;
+
+    // V1 handed out editions 1 through supply, including reserved spots, without EditionMarkers.
+    if master_edition.supply > 0 {
+        for marker_number in 0..=master_edition.supply / EDITION_MARKER_BIT_SIZE {
+            let edition_marker_info = next_account_info(account_info_iter)?;
+            let mut edition_marker = load_or_create_edition_marker(
+                program_id,
+                edition_marker_info,
+                &metadata.mint,
+                marker_number,
+                payer_info,
+                system_account_info,
+                rent_info,
+            )?;
+
+            let first = (marker_number * EDITION_MARKER_BIT_SIZE).max(1);
+            let last = master_edition
+                .supply
+                .min(marker_number * EDITION_MARKER_BIT_SIZE + EDITION_MARKER_BIT_SIZE - 1);
+            for edition in first..=last {
+                edition_marker.insert_edition(edition)?;
+            }
+            edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
+        }
+    }
+
+    // Spots still unprinted on the reservation lists become reserved editions for the same addresses.
+    let mut ranges = vec![];
+    for reservation_list_info in account_info_iter {
+        assert_owned_by(reservation_list_info, program_id)?;
+        let reservation_list = get_reservation_list(reservation_list_info)?;
+        if reservation_list.master_edition() != *master_edition_info.key {
+            return Err(MetadataError::ReservationListMasterEditionMismatch.into());
+        }
+
+        let supply_snapshot = match reservation_list.supply_snapshot() {
+            Some(supply_snapshot) => supply_snapshot,
+            None => continue,
+        };
+
+        // Same offsets extract_edition_number_from_deprecated_reservation_list hands editions out by
+        let mut prev_total_offsets: u64 = 0;
+        for reservation in reservation_list.reservations() {
+            if reservation.address == solana_program::system_program::id() {
+                prev_total_offsets = reservation.total_spots;
+                continue;
+            }
+
+            if reservation.spots_remaining > 0 {
+                let start = supply_snapshot
+                    .checked_add(prev_total_offsets)
+                    .and_then(|offset| offset.checked_add(1))
+                    .ok_or(MetadataError::NumericalOverflowError)?;
+                let end = start + reservation.spots_remaining - 1;
+                if end > master_edition.supply {
+                    return Err(MetadataError::InvalidEditionReservation.into());
+                }
+                ranges.push(ReservedEditionRange::new(start, end, reservation.address)?);
+            }
+
+            prev_total_offsets = prev_total_offsets
+                .checked_add(reservation.total_spots)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+        }
+    }
+
+    if !ranges.is_empty() {
+        let edition_reservation_bump = assert_derivation(
+            program_id,
+            edition_reservation_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata.mint.as_ref(),
+                EDITION_RESERVATION.as_bytes(),
+            ],
+        )?;
+        let reservation = EditionReservation {
+            key: Key::EditionReservation,
+            bump: edition_reservation_bump,
+            ranges,
+        };
+        let edition_reservation_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION_RESERVATION.as_bytes(),
+            &[edition_reservation_bump],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            edition_reservation_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            MAX_EDITION_RESERVATION_LEN,
+            edition_reservation_seeds,
+        )?;
+
+        if reservation.try_to_vec()?.len() > edition_reservation_info.data_len() {
+            return Err(MetadataError::EditionReservationFull.into());
+        }
+        reservation.serialize(&mut *edition_reservation_info.data.borrow_mut())?;
+    }
+
+    Ok(())
+}
+
+pub fn process_mint_new_edition_from_master_edition_via_legacy_printing_token<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    edition: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let new_metadata_account_info = next_account_info(account_info_iter)?;
+    let new_edition_account_info = next_account_info(account_info_iter)?;
+    let master_edition_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_marker_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let owner_account_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_metadata_account_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let printing_mint_info = next_account_info(account_info_iter)?;
+    let legacy_printing_record_info = next_account_info(account_info_iter)?;
+    let edition_reservation_info = next_account_info(account_info_iter).ok();
+    let recipient_token_account_info = next_account_info(account_info_iter).ok();
+
+    assert_token_program_matches_package(token_program_account_info)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_owned_by(master_edition_account_info, program_id)?;
+    assert_owned_by(master_metadata_account_info, program_id)?;
+    assert_owned_by(legacy_printing_record_info, program_id)?;
+    assert_signer(owner_account_info)?;
+
+    let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+    assert_derivation(
+        program_id,
+        legacy_printing_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_metadata.mint.as_ref(),
+            LEGACY_PRINTING.as_bytes(),
+        ],
+    )?;
+    let legacy_printing_record =
+        LegacyPrintingRecord::from_account_info(legacy_printing_record_info)?;
+    if legacy_printing_record.printing_mint != *printing_mint_info.key {
+        return Err(MetadataError::PrintingMintMismatch.into());
+    }
+
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if token_account.owner != *owner_account_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token_account.mint != *printing_mint_info.key {
+        return Err(MetadataError::TokenAccountMintMismatch.into());
+    }
+
+    if token_account.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    spl_token_burn(TokenBurnParams {
+        mint: printing_mint_info.clone(),
+        source: token_account_info.clone(),
+        amount: 1,
+        authority: owner_account_info.clone(),
+        authority_signer_seeds: None,
+        token_program: token_program_account_info.clone(),
+    })?;
+
+    mint_edition_from_master_edition_v2(
+        program_id,
+        master_metadata,
+        MintNewEditionFromMasterEditionViaTokenLogicArgs {
+            new_metadata_account_info,
+            new_edition_account_info,
+            master_edition_account_info,
+            mint_info,
+            edition_marker_info,
+            mint_authority_info,
+            payer_account_info,
+            owner_account_info,
+            token_account_info,
+            update_authority_info,
+            master_metadata_account_info,
+            token_program_account_info,
+            system_account_info,
+            rent_info,
+            print_delegate_record_info: None,
+            edition_reservation_info,
+            recipient_token_account_info,
+        },
+        edition,
+    )
+}
+
+/// Set or clear the template the name and uri of a master edition's prints are rendered from.
+pub fn process_set_edition_template<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetEditionTemplateArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_account_info, program_id)?;
+    assert_owned_by(master_edition_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_account_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_edition_valid(program_id, &metadata.mint, master_edition_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    let mut master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+    if let Some(template) = &args.edition_template {
+        assert_edition_template_valid(template, master_edition.max_supply)?;
+    }
+    master_edition.edition_template = args.edition_template;
+    master_edition.serialize(&mut *master_edition_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Opt in to or out of enforced royalties, recording the programs sales have to go through.
+/// Opting in freezes the token account holding the token.
+pub fn process_set_royalty_enforcement<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetRoyaltyEnforcementArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_account_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    if let Some(allowed_transfer_programs) = &args.allowed_transfer_programs {
+        if allowed_transfer_programs.len() > MAX_ALLOWED_TRANSFER_PROGRAMS {
+            return Err(MetadataError::TooManyAllowedTransferPrograms.into());
+        }
+        // Tokens can only be kept frozen if the program holds the freeze authority
+        assert_freeze_authority_is_edition(program_id, mint_info, edition_info)?;
+
+        assert_owned_by(token_account_info, &spl_token::id())?;
+        assert_token_program_matches_package(token_program_info)?;
+        let token_account: Account = assert_initialized(token_account_info)?;
+        if token_account.mint != metadata.mint {
+            return Err(MetadataError::MintMismatch.into());
+        }
+        // Any other token account of the mint is empty, so the token can't move once this is frozen.
+        if token_account.amount == 0 || token_account.amount != get_mint_supply(mint_info)? {
+            return Err(MetadataError::EnforcedTokenNotHeld.into());
+        }
+        if !token_account.is_frozen() {
+            set_token_account_frozen(
+                program_id,
+                token_account_info,
+                mint_info,
+                edition_info,
+                token_program_info,
+                true,
+            )?;
+        }
+    }
+    metadata.allowed_transfer_programs = args.allowed_transfer_programs;
+
+    save_metadata_account(&metadata, metadata_account_info)?;
+    Ok(())
+}
+
+/// Freeze a token account of a royalty enforced mint. Anyone can, since the token is
+/// only meant to be thawed once royalties are no longer enforced.
+pub fn process_freeze_enforced_token<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if metadata.mint != *mint_info.key || token_account.mint != metadata.mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if metadata.allowed_transfer_programs.is_none() {
+        return Err(MetadataError::RoyaltyEnforcementNotEnabled.into());
+    }
+
+    set_token_account_frozen(
+        program_id,
+        token_account_info,
+        mint_info,
+        edition_info,
+        token_program_info,
+        true,
+    )
+}
+
+/// Thaw a token account of a mint that no longer enforces royalties for its owner.
+pub fn process_thaw_enforced_token<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if metadata.mint != *mint_info.key || token_account.mint != metadata.mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // While royalties are enforced the token is only ever thawed by TransferEnforcedToken.
+    if metadata.allowed_transfer_programs.is_some() {
+        return Err(MetadataError::TransferProgramNotAllowed.into());
+    }
+    if token_account.owner != *owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    set_token_account_frozen(
+        program_id,
+        token_account_info,
+        mint_info,
+        edition_info,
+        token_program_info,
+        false,
+    )
+}
+
+/// Move a royalty enforced token for an allowed transfer program, thawing it only for the
+/// transfer and freezing it again in the destination before returning.
+pub fn process_transfer_enforced_token<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let source_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let transfer_program_info = next_account_info(account_info_iter)?;
+    // A token that is frozen can't be approved to a delegate, so its owner may co-sign instead.
+    let owner_info = next_account_info(account_info_iter).unwrap_or(authority_info);
+
+    assert_signer(authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(source_info, &spl_token::id())?;
+    assert_owned_by(destination_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let source: Account = assert_initialized(source_info)?;
+    let destination: Account = assert_initialized(destination_info)?;
+    if metadata.mint != *mint_info.key
+        || source.mint != metadata.mint
+        || destination.mint != metadata.mint
+    {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let allowed_transfer_programs = metadata
+        .allowed_transfer_programs
+        .as_ref()
+        .ok_or(MetadataError::RoyaltyEnforcementNotEnabled)?;
+    // Only the program itself can sign for its transfer authority, so the transfer
+    // must be a cross program invocation from an allowed program.
+    if !allowed_transfer_programs.contains(transfer_program_info.key) {
+        return Err(MetadataError::TransferProgramNotAllowed.into());
+    }
+    assert_derivation(
+        transfer_program_info.key,
+        authority_info,
+        &[TRANSFER_AUTHORITY.as_bytes()],
+    )
+    .map_err(|_| MetadataError::TransferProgramNotAllowed)?;
+
+    if source.is_frozen() {
+        set_token_account_frozen(
+            program_id,
+            source_info,
+            mint_info,
+            edition_info,
+            token_program_info,
+            false,
+        )?;
+    }
+
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program_info.key,
+            source_info.key,
+            destination_info.key,
+            owner_info.key,
+            &[],
+            source.amount,
+        )?,
+        &[
+            source_info.clone(),
+            destination_info.clone(),
+            owner_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
+
+    set_token_account_frozen(
+        program_id,
+        destination_info,
+        mint_info,
+        edition_info,
+        token_program_info,
+        true,
+    )
+}
+
+pub fn process_close_edition_accounts(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CloseEditionAccountsArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(master_edition_info, program_id)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_edition_valid(program_id, &metadata.mint, master_edition_info)?;
+    let master_edition = get_master_edition(master_edition_info)?;
+
+    for marker_number in args.edition_marker_numbers {
+        let edition_marker_info = next_account_info(account_info_iter)?;
+        assert_owned_by(edition_marker_info, program_id)?;
+        assert_derivation(
+            program_id,
+            edition_marker_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata.mint.as_ref(),
+                EDITION.as_bytes(),
+                marker_number.to_string().as_bytes(),
+            ],
+        )?;
+
+        // A set bit is all that stops its edition from being printed twice, so a marker with any
+        // bit set stays open, even once the master edition has hit its max supply.
+        let edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+        if !edition_marker.is_empty() {
+            return Err(MetadataError::EditionMarkerInUse.into());
+        }
+
+        close_account_raw(update_authority_info, edition_marker_info)?;
+    }
+
+    // Only the deprecated printing token instructions, which need a Master Edition V1, use these.
+    for reservation_list_info in account_info_iter {
+        assert_owned_by(reservation_list_info, program_id)?;
+        let reservation_list = get_reservation_list(reservation_list_info)?;
+        if reservation_list.master_edition() != *master_edition_info.key {
+            return Err(MetadataError::ReservationListMasterEditionMismatch.into());
+        }
+
+        if master_edition.key() == Key::MasterEditionV1 {
+            return Err(MetadataError::ReservationListInUse.into());
+        }
+
+        close_account_raw(update_authority_info, reservation_list_info)?;
+    }
+
+    Ok(())
+}
+
+/// Update only the fields given, so nothing else has to be resent
+pub fn process_patch_metadata_account<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: PatchMetadataAccountArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    // Only needed to propose a new update authority
+    let pending_info = next_account_info(account_info_iter).ok();
+    let rent_info = next_account_info(account_info_iter).ok();
+
+    assert_owned_by(metadata_account_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    if args.name.is_some()
+        || args.symbol.is_some()
+        || args.uri.is_some()
+        || args.seller_fee_basis_points.is_some()
+        || args.creators.is_some()
+        || args.removed_creators.is_some()
+    {
+        if !metadata.is_mutable {
+            return Err(MetadataError::DataIsImmutable.into());
+        }
+
+        let mut data = metadata.data.clone();
+        if let Some(name) = args.name {
+            data.name = name;
+        }
+        if let Some(symbol) = args.symbol {
+            data.symbol = symbol;
+        }
+        if let Some(uri) = args.uri {
+            data.uri = uri;
+        }
+        if let Some(seller_fee_basis_points) = args.seller_fee_basis_points {
+            data.seller_fee_basis_points = seller_fee_basis_points;
+        }
+        if args.creators.is_some() || args.removed_creators.is_some() {
+            data.creators = patch_creators(
+                &metadata.data.creators,
+                &args.creators.unwrap_or_default(),
+                &args.removed_creators.unwrap_or_default(),
+                update_authority_info.key,
+            )?;
+        }
+
+        assert_data_valid(
+            &data,
+            update_authority_info.key,
+            &metadata,
+            false,
+            update_authority_info.is_signer,
+            true,
+        )?;
+        metadata.data = data;
+    }
+
+    // After the data, so one patch can make the last changes and lock them in
+    if let Some(val) = args.is_mutable {
+        if val {
+            if !metadata.is_mutable {
+                return Err(MetadataError::IsMutableCanOnlyBeFlippedToFalse.into());
+            }
+        } else {
+            metadata.is_mutable = false;
+        }
+    }
+
+    save_metadata_account(&metadata, metadata_account_info)?;
+
+    // The new update authority still has to accept, as with ProposeUpdateAuthority
+    if let Some(val) = args.update_authority {
+        let pending_info = pending_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let rent_info = rent_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        process_propose_update_authority_logic(
+            program_id,
+            ProposeUpdateAuthorityLogicArgs {
+                pending_info,
+                metadata_info: metadata_account_info,
+                payer_info: payer_account_info,
+                system_account_info,
+                rent_info,
+            },
+            &metadata,
+            &val,
+        )?;
+    }
+
+    Ok(())
+}
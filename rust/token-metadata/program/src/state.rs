@@ -1,8 +1,15 @@
 use {
-    crate::{error::MetadataError, utils::try_from_slice_checked},
-    borsh::{BorshDeserialize, BorshSerialize},
+    crate::{
+        deser::meta_deser,
+        error::MetadataError,
+        utils::{try_from_slice_checked, try_from_slice_checked_min_len},
+    },
+    borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize},
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+        account_info::AccountInfo,
+        clock::UnixTimestamp,
+        entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+        program_error::ProgramError,
         pubkey::Pubkey,
     },
 };
@@ -14,13 +21,67 @@ pub const EDITION: &str = "edition";
 
 pub const RESERVATION: &str = "reservation";
 
+/// Used in seeds to make the pending update authority pda address
+pub const PENDING_UPDATE_AUTHORITY: &str = "pending_update_authority";
+
+/// Used in seeds to make the use authority record pda address
+pub const USER: &str = "user";
+
+/// Used in seeds to make the pda that burns tokens for use authorities
+pub const BURN: &str = "burn";
+
+/// Used in seeds to make the sale authority record pda address
+pub const SALE_AUTHORITY: &str = "sale_authority";
+
+/// Used in seeds to make the print delegate record pda address
+pub const PRINT_DELEGATE: &str = "print_delegate";
+
+/// Used in seeds to make the edition reservation pda address
+pub const EDITION_RESERVATION: &str = "edition_reservation";
+
+/// Used in seeds to make the pda recording a migrated master edition's printing mint
+pub const LEGACY_PRINTING: &str = "legacy_printing";
+
+/// Seed of the pda an allowed transfer program signs with to thaw a royalty enforced token
+pub const TRANSFER_AUTHORITY: &str = "transfer_authority";
+
 pub const MAX_NAME_LENGTH: usize = 32;
 
 pub const MAX_SYMBOL_LENGTH: usize = 10;
 
+/// Width uris were puffed out to in fixed size metadata accounts
 pub const MAX_URI_LENGTH: usize = 200;
 
-pub const MAX_METADATA_LEN: usize = 1 + 32 + 32 + MAX_DATA_SIZE + 1 + 1 + 9 + 172;
+/// Longest uri accepted now that metadata fields are no longer puffed out to a fixed width
+pub const MAX_VARIABLE_URI_LENGTH: usize = 1000;
+
+/// Replaced by the edition number when an edition template is rendered for a print
+pub const EDITION_NUMBER_PLACEHOLDER: &str = "{edition}";
+
+// Size of the older, fixed size metadata accounts. The trailing padding is shared by
+// optional fields appended after edition_nonce, currently only the collection (34 bytes).
+// New metadata accounts are created at MAX_VARIABLE_METADATA_LEN instead.
+pub const MAX_METADATA_LEN: usize = 1 + 32 + 32 + MAX_DATA_SIZE + 1 + 1 + 9 + 34 + 138;
+
+// Size new metadata accounts are created at, enough for the longest fields and every optional
+// field. Accounts can't be resized, so their contents are written without padding and can
+// change length within the account on update.
+pub const MAX_VARIABLE_METADATA_LEN: usize = 1
+    + 32
+    + 32
+    + MAX_VARIABLE_DATA_SIZE
+    + 1
+    + 1
+    + 2
+    + 34
+    + 18
+    + 33
+    + 5
+    + MAX_ALLOWED_TRANSFER_PROGRAMS * 32
+    + 2;
+
+// Smallest possible metadata: empty strings, no creators and no optional fields.
+pub const MIN_METADATA_LEN: usize = 1 + 32 + 32 + 4 + 4 + 4 + 2 + 1 + 1 + 1;
 
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
@@ -31,6 +92,17 @@ pub const MAX_DATA_SIZE: usize = 4
     + 2
     + 1
     + 4
+    + MAX_FIXED_CREATOR_LIMIT * MAX_CREATOR_LEN;
+
+pub const MAX_VARIABLE_DATA_SIZE: usize = 4
+    + MAX_NAME_LENGTH
+    + 4
+    + MAX_SYMBOL_LENGTH
+    + 4
+    + MAX_VARIABLE_URI_LENGTH
+    + 2
+    + 1
+    + 4
     + MAX_CREATOR_LIMIT * MAX_CREATOR_LEN;
 
 pub const MAX_EDITION_LEN: usize = 1 + 32 + 8 + 200;
@@ -40,7 +112,15 @@ pub const MAX_EDITION_LEN: usize = 1 + 32 + 8 + 200;
 // by rewriting it.
 pub const MAX_MASTER_EDITION_LEN: usize = 1 + 9 + 8 + 264;
 
-pub const MAX_CREATOR_LIMIT: usize = 5;
+// Longest serialized edition template that still fits behind a Master Edition V2's fields
+// and the bytes a converted Master Edition V1 leaves its printing mints in.
+pub const MAX_EDITION_TEMPLATE_LEN: usize = MAX_MASTER_EDITION_LEN - (1 + 8 + 9 + 64 + 1);
+
+pub const MAX_CREATOR_LIMIT: usize = 10;
+
+// Creator slots the older, fixed size metadata accounts were laid out with, only fixed
+// layouts such as MAX_METADATA_LEN use this.
+pub const MAX_FIXED_CREATOR_LIMIT: usize = 5;
 
 pub const MAX_CREATOR_LEN: usize = 32 + 1 + 1;
 
@@ -56,6 +136,25 @@ pub const MAX_EDITION_MARKER_SIZE: usize = 32;
 
 pub const EDITION_MARKER_BIT_SIZE: u64 = 248;
 
+pub const MAX_PENDING_UPDATE_AUTHORITY_LEN: usize = 1 + 32 + 32 + 32 + 32;
+
+pub const MAX_USE_AUTHORITY_RECORD_LEN: usize = 1 + 8 + 1;
+
+pub const MAX_SALE_AUTHORITY_RECORD_LEN: usize = 1 + 1;
+
+pub const MAX_PRINT_DELEGATE_RECORD_LEN: usize = 1 + 32 + 9 + 9 + 1;
+
+// Key, bump and the length of an empty list of ranges.
+pub const EDITION_RESERVATION_BASE_LEN: usize = 1 + 1 + 4;
+
+// Accounts can't be resized, so the reservation is created large enough for later ranges, as
+// large as an account created through a cross program invocation can be.
+pub const MAX_EDITION_RESERVATION_LEN: usize = MAX_PERMITTED_DATA_INCREASE;
+
+pub const MAX_LEGACY_PRINTING_RECORD_LEN: usize = 1 + 32 + 1;
+
+pub const MAX_ALLOWED_TRANSFER_PROGRAMS: usize = 5;
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
 pub enum Key {
@@ -67,6 +166,12 @@ pub enum Key {
     ReservationListV2,
     MasterEditionV2,
     EditionMarker,
+    PendingUpdateAuthority,
+    UseAuthorityRecord,
+    SaleAuthorityRecord,
+    PrintDelegateRecord,
+    EditionReservation,
+    LegacyPrintingRecord,
 }
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
@@ -84,7 +189,40 @@ pub struct Data {
 }
 
 #[repr(C)]
-#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct DataV2 {
+    /// The name of the asset
+    pub name: String,
+    /// The symbol for the asset
+    pub symbol: String,
+    /// URI pointing to JSON representing the asset
+    pub uri: String,
+    /// Royalty basis points that goes to creators in secondary sales (0-10000)
+    pub seller_fee_basis_points: u16,
+    /// Array of creators, optional
+    pub creators: Option<Vec<Creator>>,
+    /// Collection this asset claims to belong to, optional
+    pub collection: Option<Collection>,
+    /// Number of times this asset can be used, optional
+    pub uses: Option<Uses>,
+    /// Hash (for example SHA-256) of the JSON the uri points at, optional
+    pub content_hash: Option<[u8; 32]>,
+}
+
+impl DataV2 {
+    pub fn to_v1(&self) -> Data {
+        Data {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            creators: self.creators.clone(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, Debug)]
 pub struct Metadata {
     pub key: Key,
     pub update_authority: Pubkey,
@@ -96,22 +234,42 @@ pub struct Metadata {
     pub is_mutable: bool,
     /// nonce for easy calculation of editions, if present
     pub edition_nonce: Option<u8>,
+    /// Collection this asset belongs to, only trustworthy when verified
+    pub collection: Option<Collection>,
+    /// Uses left on this asset, if it is consumable
+    pub uses: Option<Uses>,
+    /// Hash of the JSON the uri points at, only a guarantee once the metadata is immutable
+    pub content_hash: Option<[u8; 32]>,
+    /// Programs sales have to go through when royalties are enforced by keeping the token frozen
+    pub allowed_transfer_programs: Option<Vec<Pubkey>>,
+    /// What kind of token the mint is, if it was given when the metadata was created
+    pub token_standard: Option<TokenStandard>,
 }
 
 impl Metadata {
     pub fn from_account_info(a: &AccountInfo) -> Result<Metadata, ProgramError> {
-        let md: Metadata =
-            try_from_slice_checked(&a.data.borrow_mut(), Key::MetadataV1, MAX_METADATA_LEN)?;
+        let md: Metadata = try_from_slice_checked_min_len(
+            &a.data.borrow_mut(),
+            Key::MetadataV1,
+            MIN_METADATA_LEN,
+        )?;
 
         Ok(md)
     }
 }
 
+impl BorshDeserialize for Metadata {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, BorshError> {
+        meta_deser(buf)
+    }
+}
+
 pub trait MasterEdition {
     fn key(&self) -> Key;
     fn supply(&self) -> u64;
     fn set_supply(&mut self, supply: u64);
     fn max_supply(&self) -> Option<u64>;
+    fn edition_template(&self) -> Option<EditionTemplate>;
     fn save(&self, account: &AccountInfo) -> ProgramResult;
 }
 
@@ -134,6 +292,12 @@ pub struct MasterEditionV2 {
     pub supply: u64,
 
     pub max_supply: Option<u64>,
+
+    /// Unused, but a Master Edition V1 converted in place still has its printing mints here
+    pub reserved: [u8; 64],
+
+    /// Name and uri given to each print of this master edition, if they differ per edition
+    pub edition_template: Option<EditionTemplate>,
 }
 
 impl MasterEdition for MasterEditionV2 {
@@ -153,6 +317,10 @@ impl MasterEdition for MasterEditionV2 {
         self.max_supply
     }
 
+    fn edition_template(&self) -> Option<EditionTemplate> {
+        self.edition_template.clone()
+    }
+
     fn save(&self, account: &AccountInfo) -> ProgramResult {
         self.serialize(&mut *account.data.borrow_mut())?;
         Ok(())
@@ -209,6 +377,10 @@ impl MasterEdition for MasterEditionV1 {
         self.max_supply
     }
 
+    fn edition_template(&self) -> Option<EditionTemplate> {
+        None
+    }
+
     fn set_supply(&mut self, supply: u64) {
         self.supply = supply;
     }
@@ -265,6 +437,67 @@ pub struct Creator {
     pub share: u8,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct Collection {
+    /// Set by VerifyCollection once the collection's update authority has signed off
+    pub verified: bool,
+    /// Mint of the collection's own NFT, which must be a unique (max supply 0) master edition
+    pub key: Pubkey,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum UseMethod {
+    /// The token is burned once the last use is spent
+    Burn,
+    /// The token can be used any number of times up to its total
+    Multiple,
+    /// The token can be used exactly once
+    Single,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum TokenStandard {
+    /// A one of one or a print of a master edition, with no decimals
+    NonFungible,
+    /// A whole number of interchangeable items, such as in game gear, with no decimals
+    FungibleAsset,
+    /// A plain SPL token, which has no creators or royalties
+    Fungible,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    /// Uses left, decremented by Utilize
+    pub remaining: u64,
+    /// Uses the asset started out with
+    pub total: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct EditionTemplate {
+    /// Name of each print, EDITION_NUMBER_PLACEHOLDER stands in for its edition number
+    pub name: String,
+    /// Uri of each print, EDITION_NUMBER_PLACEHOLDER stands in for its edition number
+    pub uri: String,
+}
+
+impl EditionTemplate {
+    /// The name and uri of the print with this edition number
+    pub fn render(&self, edition: u64) -> (String, String) {
+        let edition = edition.to_string();
+        (
+            self.name.replace(EDITION_NUMBER_PLACEHOLDER, &edition),
+            self.uri.replace(EDITION_NUMBER_PLACEHOLDER, &edition),
+        )
+    }
+}
+
 pub trait ReservationList {
     fn master_edition(&self) -> Pubkey;
     fn supply_snapshot(&self) -> Option<u64>;
@@ -608,4 +841,206 @@ impl EditionMarker {
         self.ledger[index] = self.ledger[index] | mask;
         Ok(())
     }
+
+    pub fn remove_edition(&mut self, edition: u64) -> ProgramResult {
+        let (index, mask) = EditionMarker::get_index_and_mask(edition)?;
+        // bitwise and the inverted mask to clear a 0 into our position
+        self.ledger[index] &= !mask;
+        Ok(())
+    }
+
+    /// No edition in this marker's range is taken, so it guards nothing
+    pub fn is_empty(&self) -> bool {
+        self.ledger.iter().all(|byte| *byte == 0)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// A proposed update authority change that only takes effect once the new authority accepts it.
+pub struct PendingUpdateAuthority {
+    pub key: Key,
+    /// Metadata whose update authority is being handed over
+    pub metadata: Pubkey,
+    /// Update authority at the time of the proposal, the transfer goes stale if this changes
+    pub update_authority: Pubkey,
+    /// Key that must sign to accept the transfer
+    pub new_update_authority: Pubkey,
+    /// Funded the pending record and gets its rent back when it is closed
+    pub payer: Pubkey,
+}
+
+impl PendingUpdateAuthority {
+    pub fn from_account_info(a: &AccountInfo) -> Result<PendingUpdateAuthority, ProgramError> {
+        let pending: PendingUpdateAuthority = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::PendingUpdateAuthority,
+            MAX_PENDING_UPDATE_AUTHORITY_LEN,
+        )?;
+
+        Ok(pending)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Lets a key other than the token owner spend uses on an asset, up to a limit set by the owner.
+pub struct UseAuthorityRecord {
+    pub key: Key,
+    /// Uses this authority may still spend
+    pub allowed_uses: u64,
+    pub bump: u8,
+}
+
+impl UseAuthorityRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<UseAuthorityRecord, ProgramError> {
+        let record: UseAuthorityRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::UseAuthorityRecord,
+            MAX_USE_AUTHORITY_RECORD_LEN,
+        )?;
+
+        Ok(record)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Lets a key other than the update authority, such as a marketplace's pda, mark the primary sale as happened.
+pub struct SaleAuthorityRecord {
+    pub key: Key,
+    pub bump: u8,
+}
+
+impl SaleAuthorityRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<SaleAuthorityRecord, ProgramError> {
+        let record: SaleAuthorityRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::SaleAuthorityRecord,
+            MAX_SALE_AUTHORITY_RECORD_LEN,
+        )?;
+
+        Ok(record)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Lets a key other than the holder of a master edition's token print editions from it.
+pub struct PrintDelegateRecord {
+    pub key: Key,
+    /// Owner of the master edition token when the delegate was approved, the record lapses if it moves
+    pub holder: Pubkey,
+    /// Prints the delegate may still make, unlimited if None
+    pub remaining_prints: Option<u64>,
+    /// Unix timestamp after which the delegate may no longer print, never if None
+    pub expiry: Option<UnixTimestamp>,
+    pub bump: u8,
+}
+
+impl PrintDelegateRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<PrintDelegateRecord, ProgramError> {
+        let record: PrintDelegateRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::PrintDelegateRecord,
+            MAX_PRINT_DELEGATE_RECORD_LEN,
+        )?;
+
+        Ok(record)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Edition numbers of a Master Edition V2 that may only be printed to given wallets.
+pub struct EditionReservation {
+    pub key: Key,
+    pub bump: u8,
+    pub ranges: Vec<ReservedEditionRange>,
+}
+
+impl EditionReservation {
+    pub fn from_account_info(a: &AccountInfo) -> Result<EditionReservation, ProgramError> {
+        let reservation: EditionReservation = try_from_slice_checked_min_len(
+            &a.data.borrow_mut(),
+            Key::EditionReservation,
+            EDITION_RESERVATION_BASE_LEN,
+        )?;
+
+        Ok(reservation)
+    }
+
+    pub fn range_for(&mut self, edition: u64) -> Option<&mut ReservedEditionRange> {
+        self.ranges
+            .iter_mut()
+            .find(|range| range.start <= edition && edition <= range.end)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ReservedEditionRange {
+    /// First reserved edition number
+    pub start: u64,
+    /// Last reserved edition number, inclusive
+    pub end: u64,
+    /// Wallet that must hold each print made with these edition numbers
+    pub recipient: Pubkey,
+    /// One bit per edition number in the range, set once it has been printed
+    pub printed: Vec<u8>,
+}
+
+impl ReservedEditionRange {
+    pub fn new(start: u64, end: u64, recipient: Pubkey) -> Result<Self, ProgramError> {
+        let len = end
+            .checked_sub(start)
+            .ok_or(MetadataError::InvalidEditionReservation)?
+            .checked_div(8)
+            .and_then(|bytes| bytes.checked_add(1))
+            .ok_or(MetadataError::NumericalOverflowError)?;
+
+        Ok(ReservedEditionRange {
+            start,
+            end,
+            recipient,
+            printed: vec![0; len as usize],
+        })
+    }
+
+    fn get_index_and_mask(&self, edition: u64) -> (usize, u8) {
+        let offset = edition - self.start;
+        ((offset / 8) as usize, 1 << (offset % 8))
+    }
+
+    pub fn edition_printed(&self, edition: u64) -> bool {
+        let (index, mask) = self.get_index_and_mask(edition);
+        self.printed[index] & mask != 0
+    }
+
+    pub fn mark_printed(&mut self, edition: u64) {
+        let (index, mask) = self.get_index_and_mask(edition);
+        self.printed[index] |= mask;
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Left behind when a Master Edition V1 is migrated, so its outstanding printing tokens can
+/// still be burned for prints.
+pub struct LegacyPrintingRecord {
+    pub key: Key,
+    pub printing_mint: Pubkey,
+    pub bump: u8,
+}
+
+impl LegacyPrintingRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<LegacyPrintingRecord, ProgramError> {
+        let record: LegacyPrintingRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::LegacyPrintingRecord,
+            MAX_LEGACY_PRINTING_RECORD_LEN,
+        )?;
+
+        Ok(record)
+    }
 }
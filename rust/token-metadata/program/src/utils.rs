@@ -4,10 +4,14 @@ use {
     crate::{
         error::MetadataError,
         state::{
-            get_reservation_list, Data, EditionMarker, Key, MasterEditionV1, Metadata, EDITION,
-            EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN, MAX_EDITION_MARKER_SIZE,
-            MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
-            MAX_URI_LENGTH, PREFIX,
+            get_master_edition, get_reservation_list, Collection, Creator, Data, DataV2,
+            EditionMarker, EditionReservation, EditionTemplate, Key, MasterEditionV1, Metadata,
+            PendingUpdateAuthority, PrintDelegateRecord, TokenStandard, UseMethod, Uses, EDITION,
+            EDITION_MARKER_BIT_SIZE, EDITION_RESERVATION, MAX_CREATOR_LIMIT, MAX_EDITION_LEN,
+            MAX_EDITION_MARKER_SIZE, MAX_EDITION_TEMPLATE_LEN, MAX_MASTER_EDITION_LEN,
+            MAX_NAME_LENGTH, MAX_PENDING_UPDATE_AUTHORITY_LEN, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+            MAX_VARIABLE_METADATA_LEN, MAX_VARIABLE_URI_LENGTH, PENDING_UPDATE_AUTHORITY, PREFIX,
+            PRINT_DELEGATE,
         },
     },
     solana_program::{
@@ -21,7 +25,7 @@ use {
         program_pack::{IsInitialized, Pack},
         pubkey::Pubkey,
         system_instruction,
-        sysvar::{rent::Rent, Sysvar},
+        sysvar::{clock::Clock, rent::Rent, Sysvar},
     },
     spl_token::{
         instruction::{set_authority, AuthorityType},
@@ -46,7 +50,7 @@ pub fn assert_data_valid(
         return Err(MetadataError::SymbolTooLong.into());
     }
 
-    if data.uri.len() > MAX_URI_LENGTH {
+    if data.uri.len() > MAX_VARIABLE_URI_LENGTH {
         return Err(MetadataError::UriTooLong.into());
     }
 
@@ -54,6 +58,12 @@ pub fn assert_data_valid(
         return Err(MetadataError::InvalidBasisPoints.into());
     }
 
+    if existing_metadata.token_standard == Some(TokenStandard::Fungible)
+        && (data.creators.is_some() || data.seller_fee_basis_points != 0)
+    {
+        return Err(MetadataError::FungibleCannotHaveCreatorsOrRoyalties.into());
+    }
+
     if data.creators.is_some() {
         if let Some(creators) = &data.creators {
             if creators.len() > MAX_CREATOR_LIMIT {
@@ -133,6 +143,94 @@ pub fn assert_data_valid(
     Ok(())
 }
 
+pub fn assert_collection_update_is_valid(
+    existing: &Option<Collection>,
+    incoming: &Option<Collection>,
+) -> ProgramResult {
+    // A verified collection may only be left untouched; it has to be unverified before it can change.
+    if let Some(existing_collection) = existing {
+        if existing_collection.verified {
+            if incoming != existing {
+                return Err(MetadataError::VerifiedCollectionCannotBeChanged.into());
+            }
+            return Ok(());
+        }
+    }
+
+    if let Some(incoming_collection) = incoming {
+        if incoming_collection.verified {
+            return Err(MetadataError::CollectionCannotBeVerifiedInThisInstruction.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_valid_use(incoming: &Option<Uses>, existing: &Option<Uses>) -> ProgramResult {
+    if incoming == existing {
+        return Ok(());
+    }
+
+    // Once a use has been spent the uses are locked in.
+    if let Some(existing_uses) = existing {
+        if existing_uses.remaining != existing_uses.total {
+            return Err(MetadataError::CannotChangeUsesAfterFirstUse.into());
+        }
+    }
+
+    if let Some(uses) = incoming {
+        if uses.total == 0
+            || uses.remaining != uses.total
+            || (uses.use_method == UseMethod::Single && uses.total != 1)
+        {
+            return Err(MetadataError::InvalidUseMethod.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_collection_verify_is_valid(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    collection_authority_info: &AccountInfo,
+    collection_mint_info: &AccountInfo,
+    collection_metadata_info: &AccountInfo,
+    collection_edition_info: &AccountInfo,
+) -> ProgramResult {
+    assert_signer(collection_authority_info)?;
+    assert_owned_by(collection_mint_info, &spl_token::id())?;
+    assert_owned_by(collection_metadata_info, program_id)?;
+    assert_owned_by(collection_edition_info, program_id)?;
+
+    match &metadata.collection {
+        Some(collection) => {
+            if collection.key != *collection_mint_info.key {
+                return Err(MetadataError::CollectionMismatch.into());
+            }
+        }
+        None => return Err(MetadataError::CollectionNotFound.into()),
+    }
+
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    if collection_metadata.mint != *collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    assert_update_authority_is_correct(&collection_metadata, collection_authority_info)?;
+
+    assert_edition_valid(
+        program_id,
+        collection_mint_info.key,
+        collection_edition_info,
+    )?;
+    let collection_edition = get_master_edition(collection_edition_info)?;
+    if collection_edition.max_supply() != Some(0) {
+        return Err(MetadataError::CollectionMustBeAUniqueMasterEdition.into());
+    }
+
+    Ok(())
+}
+
 /// assert initialized account
 pub fn assert_initialized<T: Pack + IsInitialized>(
     account_info: &AccountInfo,
@@ -194,6 +292,47 @@ pub fn create_or_allocate_account_raw<'a>(
     Ok(())
 }
 
+/// Write metadata to its account. Accounts can't be resized, so older, fixed size accounts
+/// may not have room for longer contents. Whatever follows the metadata is zeroed so that it
+/// doesn't read as optional fields.
+pub fn save_metadata_account(
+    metadata: &Metadata,
+    metadata_account_info: &AccountInfo,
+) -> ProgramResult {
+    let serialized = metadata.try_to_vec()?;
+    let mut data = metadata_account_info.try_borrow_mut_data()?;
+    if serialized.len() > data.len() {
+        return Err(MetadataError::MetadataTooLargeForAccount.into());
+    }
+
+    data[..serialized.len()].copy_from_slice(&serialized);
+    for byte in data[serialized.len()..].iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
+
+/// Drain all lamports from an account this program owns into another and wipe its data,
+/// so the runtime reclaims it at the end of the transaction.
+pub fn close_account_raw(
+    dest_account_info: &AccountInfo,
+    src_account_info: &AccountInfo,
+) -> ProgramResult {
+    let dest_starting_lamports = dest_account_info.lamports();
+    **dest_account_info.lamports.borrow_mut() = dest_starting_lamports
+        .checked_add(src_account_info.lamports())
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    **src_account_info.lamports.borrow_mut() = 0;
+
+    let mut src_data = src_account_info.data.borrow_mut();
+    for byte in src_data.iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
+
 pub fn assert_update_authority_is_correct(
     metadata: &Metadata,
     update_authority_info: &AccountInfo,
@@ -209,6 +348,270 @@ pub fn assert_update_authority_is_correct(
     Ok(())
 }
 
+/// Apply a patch to a creator list. Removals go first, then creators already on the list take
+/// their new share and the rest are added unverified, unless they are the update authority
+/// verifying itself. Returns None once no creators remain.
+pub fn patch_creators(
+    existing: &Option<Vec<Creator>>,
+    upserts: &[Creator],
+    removed: &[Pubkey],
+    update_authority: &Pubkey,
+) -> Result<Option<Vec<Creator>>, ProgramError> {
+    let mut creators = existing.clone().unwrap_or_default();
+
+    for address in removed {
+        let before = creators.len();
+        creators.retain(|c| c.address != *address);
+        if creators.len() == before {
+            return Err(MetadataError::CreatorNotFound.into());
+        }
+    }
+
+    for incoming in upserts {
+        let self_verified = incoming.verified && incoming.address == *update_authority;
+        match creators.iter_mut().find(|c| c.address == incoming.address) {
+            Some(creator) => {
+                creator.share = incoming.share;
+                creator.verified = creator.verified || self_verified;
+            }
+            None => creators.push(Creator {
+                address: incoming.address,
+                verified: self_verified,
+                share: incoming.share,
+            }),
+        }
+    }
+
+    if creators.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(creators))
+}
+
+/// Drop the given unverified creators, handing their shares to the creators that remain in
+/// proportion to what they already hold, or evenly if they hold none. Any share lost to rounding
+/// goes to the first remaining creators. Returns None once no creators remain.
+pub fn remove_unverified_creators(
+    creators: &[Creator],
+    to_remove: &[Pubkey],
+) -> Result<Option<Vec<Creator>>, ProgramError> {
+    for address in to_remove {
+        match creators.iter().find(|c| c.address == *address) {
+            Some(creator) => {
+                if creator.verified {
+                    return Err(MetadataError::CannotRemoveVerifiedCreator.into());
+                }
+            }
+            None => return Err(MetadataError::CreatorNotFound.into()),
+        }
+    }
+
+    let mut remaining: Vec<Creator> = creators
+        .iter()
+        .filter(|c| !to_remove.contains(&c.address))
+        .cloned()
+        .collect();
+
+    if remaining.is_empty() {
+        return Ok(None);
+    }
+
+    let total: u16 = creators.iter().map(|c| c.share as u16).sum();
+    let kept: u16 = remaining.iter().map(|c| c.share as u16).sum();
+    let freed = total - kept;
+
+    let mut shares: Vec<u16> = remaining
+        .iter()
+        .map(|c| {
+            let extra = if kept == 0 {
+                freed / remaining.len() as u16
+            } else {
+                freed * c.share as u16 / kept
+            };
+            c.share as u16 + extra
+        })
+        .collect();
+
+    let given: u16 = shares.iter().sum::<u16>() - kept;
+    for share in shares.iter_mut().take((freed - given) as usize) {
+        *share += 1;
+    }
+
+    for (creator, share) in remaining.iter_mut().zip(shares) {
+        creator.share = share
+            .try_into()
+            .map_err(|_| MetadataError::NumericalOverflowError)?;
+    }
+
+    Ok(Some(remaining))
+}
+
+/// Checks that a print delegate approved by the current holder of the master token may still
+/// print, counting the print against its cap.
+pub fn use_print_delegate(
+    program_id: &Pubkey,
+    print_delegate_record_info: &AccountInfo,
+    print_delegate_info: &AccountInfo,
+    master_mint: &Pubkey,
+    holder: &Pubkey,
+) -> ProgramResult {
+    assert_owned_by(print_delegate_record_info, program_id)?;
+    assert_derivation(
+        program_id,
+        print_delegate_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            PRINT_DELEGATE.as_bytes(),
+            print_delegate_info.key.as_ref(),
+        ],
+    )?;
+
+    let mut record = PrintDelegateRecord::from_account_info(print_delegate_record_info)?;
+
+    if record.holder != *holder {
+        return Err(MetadataError::InvalidPrintDelegate.into());
+    }
+
+    if let Some(expiry) = record.expiry {
+        if Clock::get()?.unix_timestamp >= expiry {
+            return Err(MetadataError::PrintDelegateExpired.into());
+        }
+    }
+
+    if let Some(remaining_prints) = record.remaining_prints {
+        if remaining_prints == 0 {
+            return Err(MetadataError::PrintDelegateCapReached.into());
+        }
+        record.remaining_prints = Some(remaining_prints - 1);
+        record.serialize(&mut *print_delegate_record_info.data.borrow_mut())?;
+    }
+
+    Ok(())
+}
+
+/// Loads the EditionMarker covering editions marker_number * EDITION_MARKER_BIT_SIZE onwards,
+/// creating it first if no edition it covers has been printed or reserved yet.
+pub fn load_or_create_edition_marker<'a>(
+    program_id: &Pubkey,
+    edition_marker_info: &AccountInfo<'a>,
+    master_mint: &Pubkey,
+    marker_number: u64,
+    payer_account_info: &AccountInfo<'a>,
+    system_account_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+) -> Result<EditionMarker, ProgramError> {
+    let as_string = marker_number.to_string();
+    let bump = assert_derivation(
+        program_id,
+        edition_marker_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+    )?;
+
+    if edition_marker_info.data_is_empty() {
+        let seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+            &[bump],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            edition_marker_info,
+            rent_info,
+            system_account_info,
+            payer_account_info,
+            MAX_EDITION_MARKER_SIZE,
+            seeds,
+        )?;
+    }
+
+    let mut edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+    edition_marker.key = Key::EditionMarker;
+
+    Ok(edition_marker)
+}
+
+/// Prints a reserved edition, which reserving already marked as taken on its EditionMarker,
+/// once the new mint is held by the wallet the edition was reserved for.
+pub fn claim_reserved_edition(
+    program_id: &Pubkey,
+    edition_reservation_info: &AccountInfo,
+    recipient_token_account_info: &AccountInfo,
+    master_mint: &Pubkey,
+    new_mint: &Pubkey,
+    edition: u64,
+) -> ProgramResult {
+    assert_owned_by(edition_reservation_info, program_id)?;
+    assert_owned_by(recipient_token_account_info, &spl_token::id())?;
+    assert_derivation(
+        program_id,
+        edition_reservation_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION_RESERVATION.as_bytes(),
+        ],
+    )?;
+
+    let mut reservation = EditionReservation::from_account_info(edition_reservation_info)?;
+    let range = reservation
+        .range_for(edition)
+        .ok_or(MetadataError::EditionNotReserved)?;
+
+    if range.edition_printed(edition) {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    let token_account: Account = assert_initialized(recipient_token_account_info)?;
+    if token_account.mint != *new_mint
+        || token_account.owner != range.recipient
+        || token_account.amount != 1
+    {
+        return Err(MetadataError::ReservedEditionRecipientMismatch.into());
+    }
+
+    range.mark_printed(edition);
+    reservation.serialize(&mut *edition_reservation_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Checks that owner holds the metadata's mint in the given token account
+pub fn assert_holds_asset(
+    metadata: &Metadata,
+    token_account_info: &AccountInfo,
+    owner_info: &AccountInfo,
+) -> ProgramResult {
+    let token_account: Account = assert_initialized(token_account_info)?;
+
+    if token_account.mint != metadata.mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token_account.owner != *owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token_account.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    Ok(())
+}
+
 /// Unpacks COption from a slice, taken from token program
 fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
     let (tag, body) = array_refs![src, 4, 32];
@@ -353,6 +756,74 @@ pub fn transfer_mint_authority<'a>(
     Ok(())
 }
 
+/// Check the mint's freeze authority is its edition pda, returning the edition's bump seed.
+pub fn assert_freeze_authority_is_edition(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    edition_info: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let bump = assert_derivation(
+        program_id,
+        edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    match get_mint_freeze_authority(mint_info)? {
+        COption::Some(key) if key == *edition_info.key => Ok(bump),
+        _ => Err(MetadataError::FreezeAuthorityNotEdition.into()),
+    }
+}
+
+/// Freeze or thaw a token account of a mint whose freeze authority is its edition.
+pub fn set_token_account_frozen<'a>(
+    program_id: &Pubkey,
+    token_account_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    edition_info: &AccountInfo<'a>,
+    token_program_info: &AccountInfo<'a>,
+    frozen: bool,
+) -> ProgramResult {
+    let bump = assert_freeze_authority_is_edition(program_id, mint_info, edition_info)?;
+    let edition_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        &[bump],
+    ];
+    let instruction = if frozen {
+        spl_token::instruction::freeze_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?
+    } else {
+        spl_token::instruction::thaw_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+            token_program_info.clone(),
+        ],
+        &[edition_authority_seeds],
+    )
+}
+
 pub fn assert_rent_exempt(rent: &Rent, account_info: &AccountInfo) -> ProgramResult {
     if !rent.is_exempt(account_info.lamports(), account_info.data_len()) {
         Err(MetadataError::NotRentExempt.into())
@@ -542,9 +1013,6 @@ pub fn mint_limited_edition<'a>(
     edition_override: Option<u64>,
 ) -> ProgramResult {
     let me_supply = get_supply_off_master_edition(master_edition_account_info)?;
-    let mint_authority = get_mint_authority(mint_info)?;
-    let mint_supply = get_mint_supply(mint_info)?;
-    assert_mint_authority_matches_mint(&mint_authority, mint_authority_info)?;
 
     assert_edition_valid(
         program_id,
@@ -552,17 +1020,6 @@ pub fn mint_limited_edition<'a>(
         master_edition_account_info,
     )?;
 
-    let edition_seeds = &[
-        PREFIX.as_bytes(),
-        program_id.as_ref(),
-        &mint_info.key.as_ref(),
-        EDITION.as_bytes(),
-    ];
-    let (edition_key, bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
-    if edition_key != *new_edition_account_info.key {
-        return Err(MetadataError::InvalidEditionKey.into());
-    }
-
     if reservation_list_info.is_some() && edition_override.is_some() {
         return Err(MetadataError::InvalidOperation.into());
     }
@@ -574,10 +1031,81 @@ pub fn mint_limited_edition<'a>(
         me_supply,
     )?;
 
+    let edition = calculate_edition_number(
+        mint_authority_info,
+        reservation_list_info,
+        edition_override,
+        me_supply,
+    )?;
+
+    create_limited_edition(
+        program_id,
+        master_metadata,
+        new_metadata_account_info,
+        new_edition_account_info,
+        master_edition_account_info,
+        mint_info,
+        mint_authority_info,
+        payer_account_info,
+        update_authority_info,
+        token_program_account_info,
+        system_account_info,
+        rent_info,
+        edition,
+    )
+}
+
+/// Creates the Metadata and Edition of a single print once the master edition's supply
+/// has been accounted for.
+#[allow(clippy::too_many_arguments)]
+pub fn create_limited_edition<'a>(
+    program_id: &'a Pubkey,
+    master_metadata: Metadata,
+    new_metadata_account_info: &'a AccountInfo<'a>,
+    new_edition_account_info: &'a AccountInfo<'a>,
+    master_edition_account_info: &'a AccountInfo<'a>,
+    mint_info: &'a AccountInfo<'a>,
+    mint_authority_info: &'a AccountInfo<'a>,
+    payer_account_info: &'a AccountInfo<'a>,
+    update_authority_info: &'a AccountInfo<'a>,
+    token_program_account_info: &'a AccountInfo<'a>,
+    system_account_info: &'a AccountInfo<'a>,
+    rent_info: &'a AccountInfo<'a>,
+    edition: u64,
+) -> ProgramResult {
+    let mint_authority = get_mint_authority(mint_info)?;
+    let mint_supply = get_mint_supply(mint_info)?;
+    assert_mint_authority_matches_mint(&mint_authority, mint_authority_info)?;
+
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+    ];
+    let (edition_key, bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
+    if edition_key != *new_edition_account_info.key {
+        return Err(MetadataError::InvalidEditionKey.into());
+    }
+
     if mint_supply != 1 {
         return Err(MetadataError::EditionsMustHaveExactlyOneToken.into());
     }
 
+    // A templated print points at content of its own, which the master's hash doesn't describe.
+    let (name, uri, content_hash) =
+        match get_master_edition(master_edition_account_info)?.edition_template() {
+            Some(template) => {
+                let (name, uri) = template.render(edition);
+                (name, uri, None)
+            }
+            None => (
+                trim_puffed_field(&master_metadata.data.name),
+                trim_puffed_field(&master_metadata.data.uri),
+                master_metadata.content_hash,
+            ),
+        };
+
     // create the metadata the normal way...
     process_create_metadata_accounts_logic(
         &program_id,
@@ -590,9 +1118,27 @@ pub fn mint_limited_edition<'a>(
             system_account_info,
             rent_info,
         },
-        master_metadata.data,
+        DataV2 {
+            name,
+            symbol: trim_puffed_field(&master_metadata.data.symbol),
+            uri,
+            seller_fee_basis_points: master_metadata.data.seller_fee_basis_points,
+            creators: master_metadata.data.creators,
+            // Prints name the master's collection, but only its authority can verify them.
+            collection: master_metadata.collection.map(|collection| Collection {
+                verified: false,
+                ..collection
+            }),
+            // and start out with all of the master's uses.
+            uses: master_metadata.uses.map(|uses| Uses {
+                remaining: uses.total,
+                ..uses
+            }),
+            content_hash,
+        },
         true,
         false,
+        master_metadata.token_standard,
     )?;
     let edition_authority_seeds = &[
         PREFIX.as_bytes(),
@@ -616,18 +1162,12 @@ pub fn mint_limited_edition<'a>(
     let edition_data = &mut new_edition_account_info.data.borrow_mut();
     let output = array_mut_ref![edition_data, 0, MAX_EDITION_LEN];
 
-    let (key, parent, edition, _padding) = mut_array_refs![output, 1, 32, 8, 200];
+    let (key, parent, edition_number_bytes, _padding) = mut_array_refs![output, 1, 32, 8, 200];
 
     *key = [Key::EditionV1 as u8];
     parent.copy_from_slice(master_edition_account_info.key.as_ref());
 
-    *edition = calculate_edition_number(
-        mint_authority_info,
-        reservation_list_info,
-        edition_override,
-        me_supply,
-    )?
-    .to_le_bytes();
+    *edition_number_bytes = edition.to_le_bytes();
 
     // Now make sure this mint can never be used by anybody else.
     transfer_mint_authority(
@@ -781,6 +1321,96 @@ pub fn try_from_slice_checked<T: BorshDeserialize>(
     Ok(result)
 }
 
+/// Like try_from_slice_checked, but for accounts that come in more than one size such as Metadata
+/// and EditionReservation, which only have to be at least `min_data_size` long.
+pub fn try_from_slice_checked_min_len<T: BorshDeserialize>(
+    data: &[u8],
+    data_type: Key,
+    min_data_size: usize,
+) -> Result<T, ProgramError> {
+    if data.len() < min_data_size
+        || (data[0] != data_type as u8 && data[0] != Key::Uninitialized as u8)
+    {
+        return Err(MetadataError::DataTypeMismatch.into());
+    }
+
+    let result: T = try_from_slice_unchecked(data)?;
+
+    Ok(result)
+}
+
+pub struct ProposeUpdateAuthorityLogicArgs<'a> {
+    pub pending_info: &'a AccountInfo<'a>,
+    pub metadata_info: &'a AccountInfo<'a>,
+    pub payer_info: &'a AccountInfo<'a>,
+    pub system_account_info: &'a AccountInfo<'a>,
+    pub rent_info: &'a AccountInfo<'a>,
+}
+
+/// Records new_update_authority as the pending update authority of a metadata, for it to accept.
+/// The caller has already checked the update authority of the metadata.
+pub fn process_propose_update_authority_logic<'a>(
+    program_id: &Pubkey,
+    accounts: ProposeUpdateAuthorityLogicArgs<'a>,
+    metadata: &Metadata,
+    new_update_authority: &Pubkey,
+) -> ProgramResult {
+    let ProposeUpdateAuthorityLogicArgs {
+        pending_info,
+        metadata_info,
+        payer_info,
+        system_account_info,
+        rent_info,
+    } = accounts;
+
+    let bump_seed = assert_derivation(
+        program_id,
+        pending_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+    )?;
+
+    let is_new_record = pending_info.data_is_empty();
+    if is_new_record {
+        let pending_authority_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+            &[bump_seed],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            pending_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            MAX_PENDING_UPDATE_AUTHORITY_LEN,
+            pending_authority_seeds,
+        )?;
+    } else {
+        assert_owned_by(pending_info, program_id)?;
+    }
+
+    let mut pending = PendingUpdateAuthority::from_account_info(pending_info)?;
+    // Proposing again reuses the record, so its rent still belongs to whoever first funded it.
+    if is_new_record {
+        pending.payer = *payer_info.key;
+    }
+    pending.key = Key::PendingUpdateAuthority;
+    pending.metadata = *metadata_info.key;
+    pending.update_authority = metadata.update_authority;
+    pending.new_update_authority = *new_update_authority;
+    pending.serialize(&mut *pending_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
 pub struct CreateMetadataAccountsLogicArgs<'a> {
     pub metadata_account_info: &'a AccountInfo<'a>,
     pub mint_info: &'a AccountInfo<'a>,
@@ -805,9 +1435,10 @@ const SEED_AUTHORITY: Pubkey = Pubkey::new_from_array([
 pub fn process_create_metadata_accounts_logic(
     program_id: &Pubkey,
     accounts: CreateMetadataAccountsLogicArgs,
-    data: Data,
+    data: DataV2,
     allow_direct_creator_writes: bool,
     mut is_mutable: bool,
+    token_standard: Option<TokenStandard>,
 ) -> ProgramResult {
     let CreateMetadataAccountsLogicArgs {
         metadata_account_info,
@@ -856,70 +1487,119 @@ pub fn process_create_metadata_accounts_logic(
         return Err(MetadataError::InvalidMetadataKey.into());
     }
 
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+    ];
+    let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
+
+    let new_metadata = Metadata {
+        key: Key::MetadataV1,
+        update_authority: update_authority_key,
+        mint: *mint_info.key,
+        data: data.to_v1(),
+        primary_sale_happened: false,
+        is_mutable,
+        edition_nonce: Some(edition_bump_seed),
+        collection: data.collection.clone(),
+        uses: data.uses.clone(),
+        content_hash: data.content_hash,
+        allowed_transfer_programs: None,
+        token_standard,
+    };
+
     create_or_allocate_account_raw(
         *program_id,
         metadata_account_info,
         rent_info,
         system_account_info,
         payer_account_info,
-        MAX_METADATA_LEN,
+        MAX_VARIABLE_METADATA_LEN,
         metadata_authority_signer_seeds,
     )?;
 
+    if let Some(token_standard) = token_standard {
+        let mint: Mint = assert_initialized(mint_info)?;
+        let decimals_match = match token_standard {
+            TokenStandard::NonFungible | TokenStandard::FungibleAsset => mint.decimals == 0,
+            TokenStandard::Fungible => true,
+        };
+        if !decimals_match {
+            return Err(MetadataError::MintDecimalsDoNotMatchTokenStandard.into());
+        }
+    }
+
     let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    // The blank account stands in for existing metadata, but the data is checked against the new standard
+    metadata.token_standard = token_standard;
     assert_data_valid(
-        &data,
+        &data.to_v1(),
         &update_authority_key,
         &metadata,
         allow_direct_creator_writes,
         update_authority_info.is_signer,
         false,
     )?;
+    assert_collection_update_is_valid(&None, &data.collection)?;
+    assert_valid_use(&data.uses, &None)?;
 
-    metadata.mint = *mint_info.key;
-    metadata.key = Key::MetadataV1;
-    metadata.data = data;
-    metadata.is_mutable = is_mutable;
-    metadata.update_authority = update_authority_key;
+    new_metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
 
-    puff_out_data_fields(&mut metadata);
+    Ok(())
+}
 
-    let edition_seeds = &[
-        PREFIX.as_bytes(),
-        program_id.as_ref(),
-        metadata.mint.as_ref(),
-        EDITION.as_bytes(),
-    ];
-    let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
-    metadata.edition_nonce = Some(edition_bump_seed);
+/// Check that every print the master edition can still make gets a name and uri that fit in metadata.
+pub fn assert_edition_template_valid(
+    template: &EditionTemplate,
+    max_supply: Option<u64>,
+) -> ProgramResult {
+    if template.try_to_vec()?.len() > MAX_EDITION_TEMPLATE_LEN {
+        return Err(MetadataError::EditionTemplateTooLong.into());
+    }
+
+    // The widest edition number a print can render
+    let (name, uri) = template.render(max_supply.unwrap_or(u64::MAX));
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(MetadataError::NameTooLong.into());
+    }
+
+    if uri.len() > MAX_VARIABLE_URI_LENGTH {
+        return Err(MetadataError::UriTooLong.into());
+    }
 
     Ok(())
 }
 
 pub fn puff_out_data_fields(metadata: &mut Metadata) {
     let mut array_of_zeroes = vec![];
-    while array_of_zeroes.len() < MAX_NAME_LENGTH - metadata.data.name.len() {
+    while array_of_zeroes.len() < MAX_NAME_LENGTH.saturating_sub(metadata.data.name.len()) {
         array_of_zeroes.push(0u8);
     }
     metadata.data.name =
         metadata.data.name.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
 
     let mut array_of_zeroes = vec![];
-    while array_of_zeroes.len() < MAX_SYMBOL_LENGTH - metadata.data.symbol.len() {
+    while array_of_zeroes.len() < MAX_SYMBOL_LENGTH.saturating_sub(metadata.data.symbol.len()) {
         array_of_zeroes.push(0u8);
     }
     metadata.data.symbol =
         metadata.data.symbol.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
 
     let mut array_of_zeroes = vec![];
-    while array_of_zeroes.len() < MAX_URI_LENGTH - metadata.data.uri.len() {
+    while array_of_zeroes.len() < MAX_URI_LENGTH.saturating_sub(metadata.data.uri.len()) {
         array_of_zeroes.push(0u8);
     }
     metadata.data.uri = metadata.data.uri.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
 }
 
+/// Strip the null padding an older, puffed metadata account carries on its strings.
+pub fn trim_puffed_field(field: &str) -> String {
+    field.trim_end_matches(char::from(0)).to_string()
+}
+
 pub struct MintNewEditionFromMasterEditionViaTokenLogicArgs<'a> {
     pub new_metadata_account_info: &'a AccountInfo<'a>,
     pub new_edition_account_info: &'a AccountInfo<'a>,
@@ -935,6 +1615,11 @@ pub struct MintNewEditionFromMasterEditionViaTokenLogicArgs<'a> {
     pub token_program_account_info: &'a AccountInfo<'a>,
     pub system_account_info: &'a AccountInfo<'a>,
     pub rent_info: &'a AccountInfo<'a>,
+    /// Lets owner_account_info be a print delegate rather than the holder of the master token
+    pub print_delegate_record_info: Option<&'a AccountInfo<'a>>,
+    /// Needed together to print an edition number reserved for recipient_token_account_info's owner
+    pub edition_reservation_info: Option<&'a AccountInfo<'a>>,
+    pub recipient_token_account_info: Option<&'a AccountInfo<'a>>,
 }
 
 pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
@@ -944,20 +1629,14 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
     ignore_owner_signer: bool,
 ) -> ProgramResult {
     let MintNewEditionFromMasterEditionViaTokenLogicArgs {
-        new_metadata_account_info,
-        new_edition_account_info,
         master_edition_account_info,
         mint_info,
-        edition_marker_info,
-        mint_authority_info,
-        payer_account_info,
         owner_account_info,
         token_account_info,
-        update_authority_info,
         master_metadata_account_info,
         token_program_account_info,
-        system_account_info,
-        rent_info,
+        print_delegate_record_info,
+        ..
     } = accounts;
 
     assert_token_program_matches_package(token_program_account_info)?;
@@ -973,7 +1652,16 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
         assert_signer(owner_account_info)?;
 
         if token_account.owner != *owner_account_info.key {
-            return Err(MetadataError::InvalidOwner.into());
+            match print_delegate_record_info {
+                Some(print_delegate_record_info) => use_print_delegate(
+                    program_id,
+                    print_delegate_record_info,
+                    owner_account_info,
+                    &master_metadata.mint,
+                    &token_account.owner,
+                )?,
+                None => return Err(MetadataError::InvalidOwner.into()),
+            }
         }
     }
 
@@ -985,6 +1673,34 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
         return Err(MetadataError::NotEnoughTokens.into());
     }
 
+    mint_edition_from_master_edition_v2(program_id, master_metadata, accounts, edition)
+}
+
+/// Takes the edition's bit on its EditionMarker, or claims it if it was reserved, then prints it.
+/// The caller has already checked its authority to print from the master edition.
+pub fn mint_edition_from_master_edition_v2<'a>(
+    program_id: &'a Pubkey,
+    master_metadata: Metadata,
+    accounts: MintNewEditionFromMasterEditionViaTokenLogicArgs<'a>,
+    edition: u64,
+) -> ProgramResult {
+    let MintNewEditionFromMasterEditionViaTokenLogicArgs {
+        new_metadata_account_info,
+        new_edition_account_info,
+        master_edition_account_info,
+        mint_info,
+        edition_marker_info,
+        mint_authority_info,
+        payer_account_info,
+        update_authority_info,
+        token_program_account_info,
+        system_account_info,
+        rent_info,
+        edition_reservation_info,
+        recipient_token_account_info,
+        ..
+    } = accounts;
+
     if !new_metadata_account_info.data_is_empty() {
         return Err(MetadataError::AlreadyInitialized.into());
     }
@@ -1032,11 +1748,23 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
     let mut edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
     edition_marker.key = Key::EditionMarker;
     if edition_marker.edition_taken(edition)? {
-        return Err(MetadataError::AlreadyInitialized.into());
+        match (edition_reservation_info, recipient_token_account_info) {
+            (Some(edition_reservation_info), Some(recipient_token_account_info)) => {
+                claim_reserved_edition(
+                    program_id,
+                    edition_reservation_info,
+                    recipient_token_account_info,
+                    &master_metadata.mint,
+                    mint_info.key,
+                    edition,
+                )?
+            }
+            _ => return Err(MetadataError::AlreadyInitialized.into()),
+        }
     } else {
-        edition_marker.insert_edition(edition)?
+        edition_marker.insert_edition(edition)?;
+        edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
     }
-    edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
 
     mint_limited_edition(
         program_id,
@@ -0,0 +1,208 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn account_closed(context: &mut ProgramTestContext, pubkey: &Pubkey) -> bool {
+    context
+        .banks_client
+        .get_account(*pubkey)
+        .await
+        .unwrap()
+        .is_none()
+}
+
+async fn create_master(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) {
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    test_master_edition.create(context, Some(10)).await.unwrap();
+}
+
+#[tokio::test]
+async fn success_master_edition() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::burn_nft(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(account_closed(&mut context, &test_metadata.pubkey).await);
+    assert!(account_closed(&mut context, &test_master_edition.pubkey).await);
+    assert!(account_closed(&mut context, &test_metadata.token.pubkey()).await);
+}
+
+#[tokio::test]
+async fn success_print_edition() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::burn_print_edition(
+            id(),
+            test_edition_marker.new_metadata_pubkey,
+            context.payer.pubkey(),
+            test_edition_marker.mint.pubkey(),
+            test_edition_marker.token.pubkey(),
+            test_edition_marker.new_edition_pubkey,
+            test_master_edition.pubkey,
+            test_metadata.mint.pubkey(),
+            1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(account_closed(&mut context, &test_edition_marker.new_metadata_pubkey).await);
+    assert!(account_closed(&mut context, &test_edition_marker.new_edition_pubkey).await);
+
+    let edition_marker = test_edition_marker.get_data(&mut context).await;
+    assert_eq!(edition_marker.ledger[0], 0);
+
+    // The edition number is free to be printed again
+    let reprint = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    reprint.create(&mut context).await.unwrap();
+}
+
+#[tokio::test]
+async fn success_master_edition_with_prints_and_update_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::burn_nft(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            Some(context.payer.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(account_closed(&mut context, &test_master_edition.pubkey).await);
+}
+
+#[tokio::test]
+async fn fail_master_edition_with_prints() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::burn_nft(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::MasterEditionHasPrints);
+}
+
+#[tokio::test]
+async fn fail_invalid_owner() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let fake_owner = Keypair::new();
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::burn_nft(
+            id(),
+            test_metadata.pubkey,
+            fake_owner.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_owner],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidOwner);
+}
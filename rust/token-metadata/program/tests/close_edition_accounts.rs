@@ -0,0 +1,317 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::{PREFIX, RESERVATION};
+use metaplex_token_metadata::{deprecated_instruction, id, instruction};
+use utils::*;
+
+async fn create_master(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) {
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    test_master_edition.create(context, Some(10)).await.unwrap();
+}
+
+async fn close_edition_accounts(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    edition_marker_numbers: Vec<u64>,
+    reservation_lists: &[Pubkey],
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::close_edition_accounts(
+            id(),
+            test_master_edition.pubkey,
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            edition_marker_numbers,
+            reservation_lists,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn create_master_v1_with_reservation_list(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) -> Pubkey {
+    let printing_mint = Keypair::new();
+    let one_time_auth_mint = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    create_mint(context, &printing_mint, &payer_pubkey, None)
+        .await
+        .unwrap();
+    create_mint(context, &one_time_auth_mint, &payer_pubkey, None)
+        .await
+        .unwrap();
+
+    let program_id = id();
+    let (reservation_list, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            test_master_edition.pubkey.as_ref(),
+            RESERVATION.as_bytes(),
+            payer_pubkey.as_ref(),
+        ],
+        &program_id,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            deprecated_instruction::deprecated_create_master_edition(
+                id(),
+                test_master_edition.pubkey,
+                test_metadata.mint.pubkey(),
+                printing_mint.pubkey(),
+                one_time_auth_mint.pubkey(),
+                payer_pubkey,
+                payer_pubkey,
+                payer_pubkey,
+                test_metadata.pubkey,
+                payer_pubkey,
+                None,
+                None,
+            ),
+            deprecated_instruction::deprecated_create_reservation_list(
+                id(),
+                reservation_list,
+                payer_pubkey,
+                payer_pubkey,
+                test_master_edition.pubkey,
+                payer_pubkey,
+                test_metadata.pubkey,
+            ),
+        ],
+        Some(&payer_pubkey),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    reservation_list
+}
+
+async fn account_closed(context: &mut ProgramTestContext, pubkey: &Pubkey) -> bool {
+    context
+        .banks_client
+        .get_account(*pubkey)
+        .await
+        .unwrap()
+        .is_none()
+}
+
+#[tokio::test]
+async fn success_burned_prints() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::burn_print_edition(
+            id(),
+            test_edition_marker.new_metadata_pubkey,
+            context.payer.pubkey(),
+            test_edition_marker.mint.pubkey(),
+            test_edition_marker.token.pubkey(),
+            test_edition_marker.new_edition_pubkey,
+            test_master_edition.pubkey,
+            test_metadata.mint.pubkey(),
+            1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    close_edition_accounts(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        vec![0],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(account_closed(&mut context, &test_edition_marker.pubkey).await);
+
+    // A new marker is made for the next print in its range
+    EditionMarker::new(&test_metadata, &test_master_edition, 1)
+        .create(&mut context)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn fail_marker_with_prints() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    create_master(&mut context, &test_metadata, &test_master_edition).await;
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let result = close_edition_accounts(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        vec![0],
+        &[],
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::EditionMarkerInUse);
+}
+
+#[tokio::test]
+async fn fail_marker_at_max_supply() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    test_master_edition
+        .create(&mut context, Some(1))
+        .await
+        .unwrap();
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let result = close_edition_accounts(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        vec![0],
+        &[],
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::EditionMarkerInUse);
+    assert!(!account_closed(&mut context, &test_edition_marker.pubkey).await);
+}
+
+#[tokio::test]
+async fn success_reservation_list_after_migration() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    let reservation_list =
+        create_master_v1_with_reservation_list(&mut context, &test_metadata, &test_master_edition)
+            .await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::migrate_master_edition_v1_to_v2(
+            id(),
+            test_master_edition.pubkey,
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            0,
+            &[reservation_list],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    close_edition_accounts(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        vec![],
+        &[reservation_list],
+    )
+    .await
+    .unwrap();
+
+    assert!(account_closed(&mut context, &reservation_list).await);
+}
+
+#[tokio::test]
+async fn fail_reservation_list_of_master_edition_v1() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    let reservation_list =
+        create_master_v1_with_reservation_list(&mut context, &test_metadata, &test_master_edition)
+            .await;
+
+    let result = close_edition_accounts(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        vec![],
+        &[reservation_list],
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::ReservationListInUse);
+}
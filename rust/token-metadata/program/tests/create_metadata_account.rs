@@ -10,7 +10,7 @@ use solana_sdk::{
     transport::TransportError,
 };
 use metaplex_token_metadata::error::MetadataError;
-use metaplex_token_metadata::state::Key;
+use metaplex_token_metadata::state::{Creator, Key, MAX_CREATOR_LIMIT};
 use metaplex_token_metadata::{id, instruction};
 use utils::*;
 
@@ -127,3 +127,65 @@ async fn fail_invalid_metadata_pda() {
 
     assert_custom_error!(result, MetadataError::InvalidMetadataKey);
 }
+
+fn creators(update_authority: Pubkey, count: usize) -> Vec<Creator> {
+    let mut creators = vec![Creator {
+        address: update_authority,
+        verified: false,
+        share: 100 - (count as u8 - 1),
+    }];
+    for _ in 1..count {
+        creators.push(Creator {
+            address: Pubkey::new_unique(),
+            verified: false,
+            share: 1,
+        });
+    }
+    creators
+}
+
+#[tokio::test]
+async fn success_max_creators() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let creators = creators(context.payer.pubkey(), MAX_CREATOR_LIMIT);
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            Some(creators.clone()),
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+
+    assert_eq!(metadata.data.creators, Some(creators));
+}
+
+#[tokio::test]
+async fn fail_too_many_creators() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let creators = creators(context.payer.pubkey(), MAX_CREATOR_LIMIT + 1);
+
+    let result = test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            Some(creators),
+            10,
+            false,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CreatorsTooLong);
+}
@@ -0,0 +1,225 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program::hash::hash;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::EditionTemplate;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_master_edition(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    max_supply: Option<u64>,
+) {
+    test_metadata
+        .create_v2(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            None,
+            None,
+            Some(hash(b"{\"name\":\"Test\"}").to_bytes()),
+        )
+        .await
+        .unwrap();
+
+    test_master_edition
+        .create(context, max_supply)
+        .await
+        .unwrap();
+}
+
+async fn set_edition_template(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    edition_template: Option<EditionTemplate>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_edition_template(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_master_edition.pubkey,
+            edition_template,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn get_print_data(
+    context: &mut ProgramTestContext,
+    test_edition_marker: &EditionMarker,
+) -> metaplex_token_metadata::state::Metadata {
+    let account = get_account(context, &test_edition_marker.new_metadata_pubkey).await;
+    try_from_slice_unchecked(&account.data).unwrap()
+}
+
+fn numbered_template() -> EditionTemplate {
+    EditionTemplate {
+        name: "Test #{edition}".to_string(),
+        uri: "https://example.com/{edition}.json".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, Some(10)).await;
+    set_edition_template(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(numbered_template()),
+    )
+    .await
+    .unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 7);
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let print = get_print_data(&mut context, &test_edition_marker).await;
+    assert_eq!(print.data.name, "Test #7");
+    assert_eq!(print.data.symbol, "TST");
+    assert_eq!(print.data.uri, "https://example.com/7.json");
+    // The master's hash is of the master's content, not of this print's
+    assert_eq!(print.content_hash, None);
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.edition_template, Some(numbered_template()));
+}
+
+#[tokio::test]
+async fn success_clear_template() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, Some(10)).await;
+    set_edition_template(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(numbered_template()),
+    )
+    .await
+    .unwrap();
+    set_edition_template(&mut context, &test_metadata, &test_master_edition, None)
+        .await
+        .unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let print = get_print_data(&mut context, &test_edition_marker).await;
+    assert_eq!(print.data.name, "Test");
+    assert_eq!(print.data.uri, "uri");
+    assert_eq!(
+        print.content_hash,
+        Some(hash(b"{\"name\":\"Test\"}").to_bytes())
+    );
+}
+
+#[tokio::test]
+async fn fail_name_too_long_for_unlimited_supply() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, None).await;
+
+    // Fits editions up to 10 but not the 20 digits an unlimited supply can reach
+    let result = set_edition_template(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(EditionTemplate {
+            name: "A long print name #{edition}".to_string(),
+            uri: "https://example.com/{edition}.json".to_string(),
+        }),
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::NameTooLong);
+}
+
+#[tokio::test]
+async fn fail_template_too_long_for_master_edition() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, Some(10)).await;
+
+    // Well within the metadata uri limit, but not within the master edition's spare space
+    let result = set_edition_template(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(EditionTemplate {
+            name: "Test #{edition}".to_string(),
+            uri: format!("https://example.com/{}/{{edition}}.json", "a".repeat(200)),
+        }),
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::EditionTemplateTooLong);
+}
+
+#[tokio::test]
+async fn fail_immutable_metadata() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    test_master_edition
+        .create(&mut context, Some(10))
+        .await
+        .unwrap();
+
+    let result = set_edition_template(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(numbered_template()),
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::DataIsImmutable);
+}
@@ -0,0 +1,136 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::hash::hash;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn freeze_metadata(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    content_hash: Option<[u8; 32]>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::freeze_metadata(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            content_hash,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+#[tokio::test]
+async fn success_freeze_with_content_hash() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let content_hash = hash(b"{\"name\":\"Test\"}").to_bytes();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    freeze_metadata(&mut context, &test_metadata, Some(content_hash))
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert!(!metadata.is_mutable);
+    assert_eq!(metadata.content_hash, Some(content_hash));
+}
+
+#[tokio::test]
+async fn success_keep_content_hash_from_creation() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let content_hash = hash(b"{\"name\":\"Test\"}").to_bytes();
+
+    test_metadata
+        .create_v2(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            None,
+            None,
+            Some(content_hash),
+        )
+        .await
+        .unwrap();
+
+    freeze_metadata(&mut context, &test_metadata, None)
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert!(!metadata.is_mutable);
+    assert_eq!(metadata.content_hash, Some(content_hash));
+}
+
+#[tokio::test]
+async fn fail_update_after_freeze() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    freeze_metadata(&mut context, &test_metadata, None)
+        .await
+        .unwrap();
+
+    let result = test_metadata
+        .update(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "new uri".to_string(),
+            None,
+            10,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::DataIsImmutable);
+
+    let result = freeze_metadata(&mut context, &test_metadata, Some([1; 32]))
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::DataIsImmutable);
+}
@@ -0,0 +1,344 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::Key;
+use metaplex_token_metadata::{deprecated_instruction, id, instruction};
+use utils::*;
+
+async fn create_master_edition_v1(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    printing_mint: &Keypair,
+    printing_token: &Keypair,
+) {
+    let one_time_auth_mint = Keypair::new();
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    create_mint(context, printing_mint, &context.payer.pubkey(), None)
+        .await
+        .unwrap();
+    create_mint(context, &one_time_auth_mint, &context.payer.pubkey(), None)
+        .await
+        .unwrap();
+    create_token_account(
+        context,
+        printing_token,
+        &printing_mint.pubkey(),
+        &context.payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            deprecated_instruction::deprecated_create_master_edition(
+                id(),
+                test_master_edition.pubkey,
+                test_metadata.mint.pubkey(),
+                printing_mint.pubkey(),
+                one_time_auth_mint.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+                None,
+                None,
+            ),
+            deprecated_instruction::deprecated_mint_printing_tokens(
+                id(),
+                printing_token.pubkey(),
+                printing_mint.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.pubkey,
+                test_master_edition.pubkey,
+                2,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+async fn print_v1_edition(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_edition: &EditionMarker,
+    printing_mint: &Keypair,
+    printing_token: &Keypair,
+) {
+    create_mint(context, &test_edition.mint, &context.payer.pubkey(), None)
+        .await
+        .unwrap();
+    create_token_account(
+        context,
+        &test_edition.token,
+        &test_edition.mint.pubkey(),
+        &context.payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        context,
+        &test_edition.mint.pubkey(),
+        &test_edition.token.pubkey(),
+        1,
+        &context.payer.pubkey(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            deprecated_instruction::deprecated_mint_new_edition_from_master_edition_via_printing_token(
+                id(),
+                test_edition.new_metadata_pubkey,
+                test_edition.new_edition_pubkey,
+                test_edition.master_edition_pubkey,
+                test_edition.mint.pubkey(),
+                context.payer.pubkey(),
+                printing_mint.pubkey(),
+                printing_token.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.pubkey,
+                None,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+async fn migrate(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    update_authority: &Keypair,
+    supply: u64,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::migrate_master_edition_v1_to_v2(
+            id(),
+            test_master_edition.pubkey,
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            update_authority.pubkey(),
+            context.payer.pubkey(),
+            supply,
+            &[],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, update_authority],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let printing_mint = Keypair::new();
+    let printing_token = Keypair::new();
+
+    create_master_edition_v1(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &printing_mint,
+        &printing_token,
+    )
+    .await;
+    print_v1_edition(
+        &mut context,
+        &test_metadata,
+        &EditionMarker::new(&test_metadata, &test_master_edition, 1),
+        &printing_mint,
+        &printing_token,
+    )
+    .await;
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    migrate(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &payer,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.key, Key::MasterEditionV2);
+    assert_eq!(master_edition.supply, 1);
+    assert_eq!(master_edition.max_supply, None);
+
+    // The remaining Printing token still redeems for a print
+    let test_edition = EditionMarker::new(&test_metadata, &test_master_edition, 2);
+    create_mint(&mut context, &test_edition.mint, &payer.pubkey(), None)
+        .await
+        .unwrap();
+    create_token_account(
+        &mut context,
+        &test_edition.token,
+        &test_edition.mint.pubkey(),
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_edition.mint.pubkey(),
+        &test_edition.token.pubkey(),
+        1,
+        &payer.pubkey(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::mint_new_edition_from_master_edition_via_legacy_printing_token(
+                id(),
+                test_edition.new_metadata_pubkey,
+                test_edition.new_edition_pubkey,
+                test_edition.master_edition_pubkey,
+                test_edition.mint.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                printing_token.pubkey(),
+                payer.pubkey(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                printing_mint.pubkey(),
+                2,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let printing_account = get_account(&mut context, &printing_token.pubkey()).await;
+    let printing_account = spl_token::state::Account::unpack(&printing_account.data).unwrap();
+    assert_eq!(printing_account.amount, 0);
+
+    // The master token prints on as a V2 master edition
+    EditionMarker::new(&test_metadata, &test_master_edition, 3)
+        .create(&mut context)
+        .await
+        .unwrap();
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.supply, 3);
+}
+
+#[tokio::test]
+async fn fail_reprint_v1_edition_number() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let printing_mint = Keypair::new();
+    let printing_token = Keypair::new();
+
+    create_master_edition_v1(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &printing_mint,
+        &printing_token,
+    )
+    .await;
+    print_v1_edition(
+        &mut context,
+        &test_metadata,
+        &EditionMarker::new(&test_metadata, &test_master_edition, 1),
+        &printing_mint,
+        &printing_token,
+    )
+    .await;
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    migrate(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &payer,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let result = EditionMarker::new(&test_metadata, &test_master_edition, 1)
+        .create(&mut context)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::AlreadyInitialized);
+}
+
+#[tokio::test]
+async fn fail_invalid_update_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let printing_mint = Keypair::new();
+    let printing_token = Keypair::new();
+
+    create_master_edition_v1(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &printing_mint,
+        &printing_token,
+    )
+    .await;
+
+    let result = migrate(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &Keypair::new(),
+        0,
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UpdateAuthorityIncorrect);
+}
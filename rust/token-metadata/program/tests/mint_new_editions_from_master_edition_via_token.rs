@@ -0,0 +1,165 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::{Edition, EDITION, PREFIX};
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_master_edition(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    max_supply: Option<u64>,
+) {
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    test_master_edition
+        .create(context, max_supply)
+        .await
+        .unwrap();
+}
+
+async fn mint_new_editions(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    first_edition: u64,
+    count: usize,
+) -> transport::Result<Vec<Pubkey>> {
+    let mut new_mints = vec![];
+    for _ in 0..count {
+        let mint = Keypair::new();
+        let token = Keypair::new();
+        create_mint(context, &mint, &context.payer.pubkey(), None).await?;
+        create_token_account(context, &token, &mint.pubkey(), &context.payer.pubkey()).await?;
+        mint_tokens(
+            context,
+            &mint.pubkey(),
+            &token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+        new_mints.push(mint.pubkey());
+    }
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::mint_new_editions_from_master_edition_via_token(
+                id(),
+                test_master_edition.pubkey,
+                &new_mints,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.token.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                first_edition,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(new_mints)
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, None).await;
+
+    // Spans two edition markers
+    let new_mints = mint_new_editions(&mut context, &test_metadata, &test_master_edition, 247, 2)
+        .await
+        .unwrap();
+
+    for (new_mint, expected_edition) in new_mints.iter().zip(247..) {
+        let (edition_pubkey, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                id().as_ref(),
+                new_mint.as_ref(),
+                EDITION.as_bytes(),
+            ],
+            &id(),
+        );
+        let account = get_account(&mut context, &edition_pubkey).await;
+        let edition: Edition = try_from_slice_unchecked(&account.data).unwrap();
+
+        assert_eq!(edition.parent, test_master_edition.pubkey);
+        assert_eq!(edition.edition, expected_edition);
+    }
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.supply, 248);
+
+    let result = EditionMarker::new(&test_metadata, &test_master_edition, 248)
+        .create(&mut context)
+        .await
+        .unwrap_err();
+    assert_custom_error!(result, MetadataError::AlreadyInitialized);
+}
+
+#[tokio::test]
+async fn fail_edition_already_printed() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, None).await;
+    EditionMarker::new(&test_metadata, &test_master_edition, 2)
+        .create(&mut context)
+        .await
+        .unwrap();
+
+    let result = mint_new_editions(&mut context, &test_metadata, &test_master_edition, 1, 2)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::AlreadyInitialized);
+}
+
+#[tokio::test]
+async fn fail_max_supply() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition, Some(2)).await;
+
+    let result = mint_new_editions(&mut context, &test_metadata, &test_master_edition, 2, 2)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::MaxEditionsMintedAlready);
+}
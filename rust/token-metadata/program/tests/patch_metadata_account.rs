@@ -0,0 +1,327 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::instruction::PatchMetadataAccountArgs;
+use metaplex_token_metadata::state::Creator;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+fn no_changes() -> PatchMetadataAccountArgs {
+    PatchMetadataAccountArgs {
+        name: None,
+        symbol: None,
+        uri: None,
+        seller_fee_basis_points: None,
+        creators: None,
+        removed_creators: None,
+        is_mutable: None,
+        update_authority: None,
+    }
+}
+
+async fn create_metadata(
+    context: &mut ProgramTestContext,
+    verified_creator: &Keypair,
+    unverified_creator: &Pubkey,
+) -> Metadata {
+    let test_metadata = Metadata::new();
+    let creators = vec![
+        Creator {
+            address: context.payer.pubkey(),
+            verified: false,
+            share: 50,
+        },
+        Creator {
+            address: verified_creator.pubkey(),
+            verified: false,
+            share: 30,
+        },
+        Creator {
+            address: *unverified_creator,
+            verified: false,
+            share: 20,
+        },
+    ];
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            Some(creators),
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::sign_metadata(
+            id(),
+            test_metadata.pubkey,
+            verified_creator.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, verified_creator],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    test_metadata
+}
+
+async fn patch(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    args: PatchMetadataAccountArgs,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::patch_metadata_account(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            args.name,
+            args.symbol,
+            args.uri,
+            args.seller_fee_basis_points,
+            args.creators,
+            args.removed_creators,
+            args.is_mutable,
+            args.update_authority,
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+#[tokio::test]
+async fn success_uri_only() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &Pubkey::new_unique()).await;
+    let before = test_metadata.get_data(&mut context).await;
+
+    patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            uri: Some("https://example.com/new.json".to_string()),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.data.uri, "https://example.com/new.json");
+    assert_eq!(metadata.data.name, before.data.name);
+    assert_eq!(metadata.data.symbol, before.data.symbol);
+    assert_eq!(metadata.data.seller_fee_basis_points, 10);
+    assert_eq!(metadata.data.creators, before.data.creators);
+    assert!(metadata.is_mutable);
+}
+
+#[tokio::test]
+async fn success_creators_keep_verification() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let unverified_creator = Pubkey::new_unique();
+    let new_creator = Pubkey::new_unique();
+    let test_metadata = create_metadata(&mut context, &creator, &unverified_creator).await;
+
+    // A stale view of the verified creator doesn't unverify them
+    patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            creators: Some(vec![
+                Creator {
+                    address: creator.pubkey(),
+                    verified: false,
+                    share: 40,
+                },
+                Creator {
+                    address: new_creator,
+                    verified: false,
+                    share: 10,
+                },
+            ]),
+            removed_creators: Some(vec![unverified_creator]),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let creators = test_metadata
+        .get_data(&mut context)
+        .await
+        .data
+        .creators
+        .unwrap();
+    assert_eq!(creators.len(), 3);
+    assert_eq!(creators[0].address, context.payer.pubkey());
+    assert_eq!(creators[0].share, 50);
+    assert!(!creators[0].verified);
+    assert_eq!(creators[1].address, creator.pubkey());
+    assert_eq!(creators[1].share, 40);
+    assert!(creators[1].verified);
+    assert_eq!(creators[2].address, new_creator);
+    assert!(!creators[2].verified);
+}
+
+#[tokio::test]
+async fn success_cannot_verify_another_creator() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let unverified_creator = Pubkey::new_unique();
+    let test_metadata = create_metadata(&mut context, &creator, &unverified_creator).await;
+
+    patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            creators: Some(vec![Creator {
+                address: unverified_creator,
+                verified: true,
+                share: 20,
+            }]),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let creators = test_metadata
+        .get_data(&mut context)
+        .await
+        .data
+        .creators
+        .unwrap();
+    assert!(!creators[2].verified);
+}
+
+#[tokio::test]
+async fn success_update_authority_is_only_proposed() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let new_update_authority = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &Pubkey::new_unique()).await;
+
+    patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            update_authority: Some(new_update_authority.pubkey()),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            new_update_authority.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_update_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+}
+
+#[tokio::test]
+async fn fail_after_made_immutable() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &Pubkey::new_unique()).await;
+
+    patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            name: Some("Final".to_string()),
+            is_mutable: Some(false),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.data.name, "Final");
+    assert!(!metadata.is_mutable);
+
+    let result = patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            uri: Some("https://example.com/new.json".to_string()),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(result, MetadataError::DataIsImmutable);
+
+    let result = patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            is_mutable: Some(true),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(result, MetadataError::IsMutableCanOnlyBeFlippedToFalse);
+}
+
+#[tokio::test]
+async fn fail_shares_not_100() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &Pubkey::new_unique()).await;
+
+    let result = patch(
+        &mut context,
+        &test_metadata,
+        PatchMetadataAccountArgs {
+            creators: Some(vec![Creator {
+                address: Pubkey::new_unique(),
+                verified: false,
+                share: 10,
+            }]),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::ShareTotalMustBe100);
+}
@@ -0,0 +1,168 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_master_edition(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) {
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    test_master_edition.create(context, Some(10)).await.unwrap();
+}
+
+async fn approve_print_delegate(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    print_delegate: &Keypair,
+    max_prints: Option<u64>,
+    expiry: Option<i64>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::approve_print_delegate(
+            id(),
+            context.payer.pubkey(),
+            test_metadata.token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            print_delegate.pubkey(),
+            context.payer.pubkey(),
+            max_prints,
+            expiry,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let print_delegate = Keypair::new();
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    approve_print_delegate(&mut context, &test_metadata, &print_delegate, None, None)
+        .await
+        .unwrap();
+
+    for edition in 1..=2 {
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, edition);
+        test_edition_marker
+            .create_via_print_delegate(&mut context, &print_delegate)
+            .await
+            .unwrap();
+    }
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.supply, 2);
+}
+
+#[tokio::test]
+async fn fail_cap_reached() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let print_delegate = Keypair::new();
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    approve_print_delegate(&mut context, &test_metadata, &print_delegate, Some(1), None)
+        .await
+        .unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    test_edition_marker
+        .create_via_print_delegate(&mut context, &print_delegate)
+        .await
+        .unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 2);
+    let result = test_edition_marker
+        .create_via_print_delegate(&mut context, &print_delegate)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::PrintDelegateCapReached);
+}
+
+#[tokio::test]
+async fn fail_expired() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let print_delegate = Keypair::new();
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    approve_print_delegate(&mut context, &test_metadata, &print_delegate, None, Some(1))
+        .await
+        .unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    let result = test_edition_marker
+        .create_via_print_delegate(&mut context, &print_delegate)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::PrintDelegateExpired);
+}
+
+#[tokio::test]
+async fn fail_revoked() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let print_delegate = Keypair::new();
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    approve_print_delegate(&mut context, &test_metadata, &print_delegate, None, None)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::revoke_print_delegate(
+            id(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            print_delegate.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    let result = test_edition_marker
+        .create_via_print_delegate(&mut context, &print_delegate)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::IncorrectOwner);
+}
@@ -0,0 +1,206 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::Creator;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_metadata(
+    context: &mut ProgramTestContext,
+    verified_creator: &Keypair,
+    unverified_creator: &Pubkey,
+    is_mutable: bool,
+) -> Metadata {
+    let test_metadata = Metadata::new();
+    let creators = vec![
+        Creator {
+            address: context.payer.pubkey(),
+            verified: false,
+            share: 40,
+        },
+        Creator {
+            address: verified_creator.pubkey(),
+            verified: false,
+            share: 35,
+        },
+        Creator {
+            address: *unverified_creator,
+            verified: false,
+            share: 25,
+        },
+    ];
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            Some(creators),
+            10,
+            is_mutable,
+        )
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::sign_metadata(
+            id(),
+            test_metadata.pubkey,
+            verified_creator.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, verified_creator],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    test_metadata
+}
+
+#[tokio::test]
+async fn success_remove_creator_verification() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &Pubkey::new_unique(), true).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_creator_verification(
+            id(),
+            test_metadata.pubkey,
+            creator.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &creator],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert!(!metadata.data.creators.unwrap()[1].verified);
+}
+
+#[tokio::test]
+async fn success_remove_unverified_creator() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let unverified_creator = Pubkey::new_unique();
+    let test_metadata = create_metadata(&mut context, &creator, &unverified_creator, true).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_unverified_creators(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            vec![unverified_creator],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let creators = metadata.data.creators.unwrap();
+
+    // 25 shares split 40:35, with the share lost to rounding going to the first creator
+    assert_eq!(creators.len(), 2);
+    assert_eq!(creators[0].address, context.payer.pubkey());
+    assert_eq!(creators[0].share, 54);
+    assert_eq!(creators[1].address, creator.pubkey());
+    assert_eq!(creators[1].share, 46);
+    assert!(creators[1].verified);
+}
+
+#[tokio::test]
+async fn fail_remove_verified_creator() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &Pubkey::new_unique(), true).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_unverified_creators(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            vec![creator.pubkey()],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CannotRemoveVerifiedCreator);
+}
+
+#[tokio::test]
+async fn fail_invalid_update_authority() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let unverified_creator = Pubkey::new_unique();
+    let fake_update_authority = Keypair::new();
+    let test_metadata = create_metadata(&mut context, &creator, &unverified_creator, true).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_unverified_creators(
+            id(),
+            test_metadata.pubkey,
+            fake_update_authority.pubkey(),
+            vec![unverified_creator],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_update_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UpdateAuthorityIncorrect);
+}
+
+#[tokio::test]
+async fn fail_immutable_metadata() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let unverified_creator = Pubkey::new_unique();
+    let test_metadata = create_metadata(&mut context, &creator, &unverified_creator, false).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_unverified_creators(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            vec![unverified_creator],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::DataIsImmutable);
+}
@@ -0,0 +1,240 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::EditionReservation;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_master_edition(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) {
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    test_master_edition
+        .create(context, Some(300))
+        .await
+        .unwrap();
+}
+
+async fn reserve_editions(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    start: u64,
+    end: u64,
+    recipient: &Pubkey,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::reserve_editions(
+            id(),
+            context.payer.pubkey(),
+            test_metadata.pubkey,
+            test_master_edition.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            start,
+            end,
+            *recipient,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let artist = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        1,
+        1,
+        &artist,
+    )
+    .await
+    .unwrap();
+    // Spans two edition markers
+    reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        240,
+        260,
+        &buyer,
+    )
+    .await
+    .unwrap();
+
+    EditionMarker::new(&test_metadata, &test_master_edition, 1)
+        .create_reserved(&mut context, &artist)
+        .await
+        .unwrap();
+    EditionMarker::new(&test_metadata, &test_master_edition, 250)
+        .create_reserved(&mut context, &buyer)
+        .await
+        .unwrap();
+    EditionMarker::new(&test_metadata, &test_master_edition, 2)
+        .create(&mut context)
+        .await
+        .unwrap();
+
+    let account = get_account(
+        &mut context,
+        &instruction::find_edition_reservation_address(&id(), &test_metadata.mint.pubkey()),
+    )
+    .await;
+    let reservation: EditionReservation = try_from_slice_unchecked(&account.data).unwrap();
+
+    assert_eq!(reservation.ranges.len(), 2);
+    assert!(reservation.ranges[0].edition_printed(1));
+    assert!(reservation.ranges[1].edition_printed(250));
+    assert!(!reservation.ranges[1].edition_printed(249));
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.supply, 250);
+}
+
+#[tokio::test]
+async fn fail_print_reserved_edition_without_reservation() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        1,
+        1,
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+
+    let result = EditionMarker::new(&test_metadata, &test_master_edition, 1)
+        .create(&mut context)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::AlreadyInitialized);
+}
+
+#[tokio::test]
+async fn fail_print_reserved_edition_to_other_wallet() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        1,
+        1,
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+
+    let result = EditionMarker::new(&test_metadata, &test_master_edition, 1)
+        .create_reserved(&mut context, &Pubkey::new_unique())
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::ReservedEditionRecipientMismatch);
+}
+
+#[tokio::test]
+async fn fail_reserve_taken_editions() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let recipient = Pubkey::new_unique();
+
+    create_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        1,
+        2,
+        &recipient,
+    )
+    .await
+    .unwrap();
+    EditionMarker::new(&test_metadata, &test_master_edition, 5)
+        .create(&mut context)
+        .await
+        .unwrap();
+
+    let result = reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        2,
+        3,
+        &recipient,
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(result, MetadataError::EditionAlreadyReserved);
+
+    let result = reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        4,
+        6,
+        &recipient,
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(result, MetadataError::EditionAlreadyReserved);
+
+    let result = reserve_editions(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        299,
+        301,
+        &recipient,
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(result, MetadataError::InvalidEditionReservation);
+}
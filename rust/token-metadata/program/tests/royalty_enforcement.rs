@@ -0,0 +1,596 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use spl_token::state::{Account, AccountState};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::TRANSFER_AUTHORITY;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+// Stands in for a marketplace: passes its instruction on to Token Metadata, whose program is the
+// first account, signing with its transfer authority.
+fn process_transfer_program(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (authority, bump) =
+        Pubkey::find_program_address(&[TRANSFER_AUTHORITY.as_bytes()], program_id);
+    let instruction = Instruction {
+        program_id: *accounts[0].key,
+        accounts: accounts[1..]
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer || *account.key == authority,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    invoke_signed(
+        &instruction,
+        accounts,
+        &[&[TRANSFER_AUTHORITY.as_bytes(), &[bump]]],
+    )
+}
+
+async fn start_with_transfer_program(transfer_program: &Pubkey) -> ProgramTestContext {
+    let mut program_test = program_test();
+    program_test.add_program(
+        "transfer_program",
+        *transfer_program,
+        processor!(process_transfer_program),
+    );
+    program_test.start_with_context().await
+}
+
+// Metadata::create makes a mint without a freeze authority, which can't enforce royalties.
+async fn create_enforceable_master_edition(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) {
+    let payer_pubkey = context.payer.pubkey();
+    create_mint(
+        context,
+        &test_metadata.mint,
+        &payer_pubkey,
+        Some(&payer_pubkey),
+    )
+    .await
+    .unwrap();
+    create_token_account(
+        context,
+        &test_metadata.token,
+        &test_metadata.mint.pubkey(),
+        &payer_pubkey,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        context,
+        &test_metadata.mint.pubkey(),
+        &test_metadata.token.pubkey(),
+        1,
+        &payer_pubkey,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::create_metadata_accounts(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            payer_pubkey,
+            payer_pubkey,
+            payer_pubkey,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            500,
+            false,
+            true,
+        )],
+        Some(&payer_pubkey),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    test_master_edition.create(context, Some(0)).await.unwrap();
+}
+
+async fn set_royalty_enforcement(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    allowed_transfer_programs: Option<Vec<Pubkey>>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_royalty_enforcement(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+            test_metadata.token.pubkey(),
+            allowed_transfer_programs,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn freeze_token(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::freeze_enforced_token(
+            id(),
+            test_metadata.token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn thaw_token_as_owner(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::thaw_enforced_token(
+            id(),
+            test_metadata.token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+// Moves the token through the transfer program, with the owner co-signing unless the transfer
+// authority owns the source.
+async fn transfer_token(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    transfer_program: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    owner: Option<&Keypair>,
+) -> transport::Result<()> {
+    let mut transfer = instruction::transfer_enforced_token(
+        id(),
+        *source,
+        *destination,
+        test_metadata.pubkey,
+        test_metadata.mint.pubkey(),
+        test_master_edition.pubkey,
+        *transfer_program,
+        owner.map(|owner| owner.pubkey()),
+    );
+    // Only the transfer program can sign for its transfer authority
+    transfer.accounts[6].is_signer = false;
+    transfer
+        .accounts
+        .insert(0, AccountMeta::new_readonly(id(), false));
+
+    let mut signers = vec![&context.payer];
+    if let Some(owner) = owner {
+        signers.push(owner);
+    }
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: *transfer_program,
+            accounts: transfer.accounts,
+            data: transfer.data,
+        }],
+        Some(&context.payer.pubkey()),
+        &signers,
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn get_token_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> Account {
+    let account = get_account(context, pubkey).await;
+    Account::unpack(&account.data).unwrap()
+}
+
+async fn get_token_state(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+) -> AccountState {
+    get_token_account(context, &test_metadata.token.pubkey())
+        .await
+        .state
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let transfer_program = Pubkey::new_unique();
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![transfer_program]),
+    )
+    .await
+    .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(
+        metadata.allowed_transfer_programs,
+        Some(vec![transfer_program])
+    );
+
+    // Opting in freezes the token where it is
+    assert_eq!(
+        get_token_state(&mut context, &test_metadata).await,
+        AccountState::Frozen
+    );
+
+    // Opting back out lets the owner thaw their own token
+    set_royalty_enforcement(&mut context, &test_metadata, &test_master_edition, None)
+        .await
+        .unwrap();
+    thaw_token_as_owner(&mut context, &test_metadata, &test_master_edition)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_state(&mut context, &test_metadata).await,
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn success_transfer_through_allowed_program() {
+    let transfer_program = Pubkey::new_unique();
+    let transfer_authority = instruction::find_transfer_authority_address(&transfer_program);
+    let mut context = start_with_transfer_program(&transfer_program).await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let owner = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    let escrow = Keypair::new();
+    let buyer_token = Keypair::new();
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![transfer_program]),
+    )
+    .await
+    .unwrap();
+    create_token_account(
+        &mut context,
+        &escrow,
+        &test_metadata.mint.pubkey(),
+        &transfer_authority,
+    )
+    .await
+    .unwrap();
+    create_token_account(
+        &mut context,
+        &buyer_token,
+        &test_metadata.mint.pubkey(),
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+
+    // Listing: the owner signs along with the transfer authority
+    transfer_token(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &transfer_program,
+        &test_metadata.token.pubkey(),
+        &escrow.pubkey(),
+        Some(&owner),
+    )
+    .await
+    .unwrap();
+
+    let source = get_token_account(&mut context, &test_metadata.token.pubkey()).await;
+    assert_eq!(source.amount, 0);
+    let listed = get_token_account(&mut context, &escrow.pubkey()).await;
+    assert_eq!(listed.amount, 1);
+    assert_eq!(listed.state, AccountState::Frozen);
+
+    // Sale: the transfer authority owns the escrow
+    transfer_token(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &transfer_program,
+        &escrow.pubkey(),
+        &buyer_token.pubkey(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let bought = get_token_account(&mut context, &buyer_token.pubkey()).await;
+    assert_eq!(bought.amount, 1);
+    assert_eq!(bought.state, AccountState::Frozen);
+}
+
+#[tokio::test]
+async fn fail_transfer_program_not_allowed() {
+    let transfer_program = Pubkey::new_unique();
+    let mut context = start_with_transfer_program(&transfer_program).await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let owner = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    let destination = Keypair::new();
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![Pubkey::new_unique()]),
+    )
+    .await
+    .unwrap();
+    create_token_account(
+        &mut context,
+        &destination,
+        &test_metadata.mint.pubkey(),
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+
+    let result = transfer_token(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &transfer_program,
+        &test_metadata.token.pubkey(),
+        &destination.pubkey(),
+        Some(&owner),
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::TransferProgramNotAllowed);
+}
+
+#[tokio::test]
+async fn fail_thaw_while_enforced() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![Pubkey::new_unique()]),
+    )
+    .await
+    .unwrap();
+
+    let result = thaw_token_as_owner(&mut context, &test_metadata, &test_master_edition)
+        .await
+        .unwrap_err();
+    assert_custom_error!(result, MetadataError::TransferProgramNotAllowed);
+}
+
+#[tokio::test]
+async fn fail_token_account_not_holder() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let empty_token = Keypair::new();
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    let payer_pubkey = context.payer.pubkey();
+    create_token_account(
+        &mut context,
+        &empty_token,
+        &test_metadata.mint.pubkey(),
+        &payer_pubkey,
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_royalty_enforcement(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+            empty_token.pubkey(),
+            Some(vec![Pubkey::new_unique()]),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::EnforcedTokenNotHeld);
+}
+
+#[tokio::test]
+async fn fail_mint_without_edition_freeze_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            500,
+            true,
+        )
+        .await
+        .unwrap();
+    test_master_edition
+        .create(&mut context, Some(0))
+        .await
+        .unwrap();
+
+    let result = set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![Pubkey::new_unique()]),
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::FreezeAuthorityNotEdition);
+}
+
+#[tokio::test]
+async fn fail_freeze_without_enforcement() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+
+    let result = freeze_token(&mut context, &test_metadata, &test_master_edition)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::RoyaltyEnforcementNotEnabled);
+}
+
+#[tokio::test]
+async fn success_opting_in_again_refreezes() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let cranker = Keypair::new();
+    let other_token = Keypair::new();
+
+    create_enforceable_master_edition(&mut context, &test_metadata, &test_master_edition).await;
+    set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![Pubkey::new_unique()]),
+    )
+    .await
+    .unwrap();
+
+    // The owner thaws while royalties aren't enforced, and turning them back on freezes it again
+    set_royalty_enforcement(&mut context, &test_metadata, &test_master_edition, None)
+        .await
+        .unwrap();
+    thaw_token_as_owner(&mut context, &test_metadata, &test_master_edition)
+        .await
+        .unwrap();
+    set_royalty_enforcement(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        Some(vec![Pubkey::new_unique()]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        get_token_state(&mut context, &test_metadata).await,
+        AccountState::Frozen
+    );
+
+    // Anyone, not just the owner, can freeze any other token account of the mint
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &cranker.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+    let payer_pubkey = context.payer.pubkey();
+    create_token_account(
+        &mut context,
+        &other_token,
+        &test_metadata.mint.pubkey(),
+        &payer_pubkey,
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::freeze_enforced_token(
+            id(),
+            other_token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            test_master_edition.pubkey,
+        )],
+        Some(&cranker.pubkey()),
+        &[&cranker],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+    assert_eq!(
+        get_token_account(&mut context, &other_token.pubkey())
+            .await
+            .state,
+        AccountState::Frozen
+    );
+}
@@ -0,0 +1,136 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::Creator;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_metadata(context: &mut ProgramTestContext, creator: Option<&Pubkey>) -> Metadata {
+    let test_metadata = Metadata::new();
+    // The update authority has to be one of the creators
+    let creators = creator.map(|creator| {
+        vec![
+            Creator {
+                address: context.payer.pubkey(),
+                verified: false,
+                share: 0,
+            },
+            Creator {
+                address: *creator,
+                verified: false,
+                share: 100,
+            },
+        ]
+    });
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            creators,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    test_metadata
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+
+    let mut metadatas = vec![];
+    for _ in 0..3 {
+        metadatas.push(create_metadata(&mut context, Some(&creator.pubkey())).await);
+    }
+    let keys: Vec<Pubkey> = metadatas.iter().map(|m| m.pubkey).collect();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::sign_metadata_batch(
+            id(),
+            creator.pubkey(),
+            &keys,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &creator],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    for test_metadata in metadatas {
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert!(metadata.data.creators.unwrap()[1].verified);
+    }
+}
+
+#[tokio::test]
+async fn fail_is_atomic() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+
+    let signable = create_metadata(&mut context, Some(&creator.pubkey())).await;
+    let other_creator = create_metadata(&mut context, Some(&Keypair::new().pubkey())).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::sign_metadata_batch(
+            id(),
+            creator.pubkey(),
+            &[signable.pubkey, other_creator.pubkey],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &creator],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CreatorNotFound);
+
+    let metadata = signable.get_data(&mut context).await;
+    assert!(!metadata.data.creators.unwrap()[1].verified);
+}
+
+#[tokio::test]
+async fn fail_no_creators() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+
+    let test_metadata = create_metadata(&mut context, None).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::sign_metadata_batch(
+            id(),
+            creator.pubkey(),
+            &[test_metadata.pubkey],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &creator],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::NoCreatorsPresentOnMetadata);
+}
@@ -0,0 +1,265 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::{Creator, DataV2, TokenStandard};
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_mint_with_decimals(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    decimals: u8,
+) -> transport::Result<()> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &context.payer.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, mint],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn create_metadata_v3(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    creators: Option<Vec<Creator>>,
+    seller_fee_basis_points: u16,
+    token_standard: TokenStandard,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::create_metadata_accounts_v3(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            creators,
+            seller_fee_basis_points,
+            true,
+            true,
+            None,
+            None,
+            None,
+            token_standard,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn mint_one_token(context: &mut ProgramTestContext, test_metadata: &Metadata) {
+    let payer_pubkey = context.payer.pubkey();
+    create_token_account(
+        context,
+        &test_metadata.token,
+        &test_metadata.mint.pubkey(),
+        &payer_pubkey,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        context,
+        &test_metadata.mint.pubkey(),
+        &test_metadata.token.pubkey(),
+        1,
+        &payer_pubkey,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn success_fungible() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_mint_with_decimals(&mut context, &test_metadata.mint, 6)
+        .await
+        .unwrap();
+    mint_one_token(&mut context, &test_metadata).await;
+    create_metadata_v3(
+        &mut context,
+        &test_metadata,
+        None,
+        0,
+        TokenStandard::Fungible,
+    )
+    .await
+    .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.token_standard, Some(TokenStandard::Fungible));
+
+    // Royalties can't be added later either
+    let result = test_metadata
+        .update_v2(
+            &mut context,
+            DataV2 {
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                uri: "uri".to_string(),
+                seller_fee_basis_points: 500,
+                creators: None,
+                collection: None,
+                uses: None,
+                content_hash: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::FungibleCannotHaveCreatorsOrRoyalties);
+
+    let result = test_master_edition
+        .create(&mut context, Some(0))
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::EditionMintDecimalsShouldBeZero);
+}
+
+#[tokio::test]
+async fn success_non_fungible_prints_keep_standard() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_mint_with_decimals(&mut context, &test_metadata.mint, 0)
+        .await
+        .unwrap();
+    mint_one_token(&mut context, &test_metadata).await;
+    create_metadata_v3(
+        &mut context,
+        &test_metadata,
+        None,
+        500,
+        TokenStandard::NonFungible,
+    )
+    .await
+    .unwrap();
+    test_master_edition
+        .create(&mut context, Some(10))
+        .await
+        .unwrap();
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let print = get_account(&mut context, &test_edition_marker.new_metadata_pubkey).await;
+    let print: metaplex_token_metadata::state::Metadata =
+        solana_program::borsh::try_from_slice_unchecked(&print.data).unwrap();
+    assert_eq!(print.token_standard, Some(TokenStandard::NonFungible));
+}
+
+#[tokio::test]
+async fn fail_fungible_asset_master_edition() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    create_mint_with_decimals(&mut context, &test_metadata.mint, 0)
+        .await
+        .unwrap();
+    mint_one_token(&mut context, &test_metadata).await;
+    create_metadata_v3(
+        &mut context,
+        &test_metadata,
+        None,
+        500,
+        TokenStandard::FungibleAsset,
+    )
+    .await
+    .unwrap();
+
+    let result = test_master_edition
+        .create(&mut context, Some(0))
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::FungibleCannotBeMasterEdition);
+}
+
+#[tokio::test]
+async fn fail_fungible_with_creators() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let creator = context.payer.pubkey();
+
+    create_mint_with_decimals(&mut context, &test_metadata.mint, 6)
+        .await
+        .unwrap();
+    let result = create_metadata_v3(
+        &mut context,
+        &test_metadata,
+        Some(vec![Creator {
+            address: creator,
+            verified: true,
+            share: 100,
+        }]),
+        0,
+        TokenStandard::Fungible,
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::FungibleCannotHaveCreatorsOrRoyalties);
+}
+
+#[tokio::test]
+async fn fail_non_fungible_with_decimals() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    create_mint_with_decimals(&mut context, &test_metadata.mint, 6)
+        .await
+        .unwrap();
+    let result = create_metadata_v3(
+        &mut context,
+        &test_metadata,
+        None,
+        500,
+        TokenStandard::NonFungible,
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::MintDecimalsDoNotMatchTokenStandard);
+}
@@ -0,0 +1,249 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn setup(context: &mut ProgramTestContext) -> Metadata {
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    test_metadata
+}
+
+async fn propose(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    new_update_authority: &Keypair,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::propose_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            new_update_authority.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = setup(&mut context).await;
+    let new_update_authority = Keypair::new();
+
+    propose(&mut context, &test_metadata, &new_update_authority).await;
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            new_update_authority.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_update_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+
+    let pending = context
+        .banks_client
+        .get_account(instruction::find_pending_update_authority_address(
+            &id(),
+            &test_metadata.mint.pubkey(),
+        ))
+        .await
+        .unwrap();
+    assert!(pending.is_none());
+}
+
+#[tokio::test]
+async fn fail_accept_by_wrong_signer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = setup(&mut context).await;
+    let new_update_authority = Keypair::new();
+    let fake_update_authority = Keypair::new();
+
+    propose(&mut context, &test_metadata, &new_update_authority).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            fake_update_authority.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_update_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::PendingUpdateAuthorityMismatch);
+}
+
+#[tokio::test]
+async fn fail_accept_after_cancel() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = setup(&mut context).await;
+    let new_update_authority = Keypair::new();
+
+    propose(&mut context, &test_metadata, &new_update_authority).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::cancel_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            new_update_authority.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_update_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::IncorrectOwner);
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+}
+
+#[tokio::test]
+async fn success_refunds_payer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = setup(&mut context).await;
+    let new_update_authority = Keypair::new();
+    let payer = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::transfer(&context.payer.pubkey(), &payer.pubkey(), 1_000_000_000),
+            instruction::propose_update_authority(
+                id(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                context.payer.pubkey(),
+                new_update_authority.pubkey(),
+                payer.pubkey(),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let pending_address =
+        instruction::find_pending_update_authority_address(&id(), &test_metadata.mint.pubkey());
+    let pending_lamports = get_account(&mut context, &pending_address).await.lamports;
+    let payer_lamports = get_account(&mut context, &payer.pubkey()).await.lamports;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            new_update_authority.pubkey(),
+            payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_update_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let account = get_account(&mut context, &payer.pubkey()).await;
+    assert_eq!(account.lamports, payer_lamports + pending_lamports);
+}
+
+#[tokio::test]
+async fn fail_refund_to_wrong_payer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = setup(&mut context).await;
+    let new_update_authority = Keypair::new();
+
+    propose(&mut context, &test_metadata, &new_update_authority).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_update_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            new_update_authority.pubkey(),
+            new_update_authority.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_update_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::PendingUpdateAuthorityPayerMismatch);
+}
@@ -99,3 +99,133 @@ async fn fail_invalid_update_authority() {
 
     assert_custom_error!(result, MetadataError::UpdateAuthorityIncorrect);
 }
+
+#[tokio::test]
+async fn success_set_update_authority_without_signature() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let new_update_authority = Keypair::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_metadata_accounts(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            Some(new_update_authority.pubkey()),
+            None,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+}
+
+#[tokio::test]
+async fn success_new_update_authority_signs() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let new_update_authority = Keypair::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_metadata_accounts_v2(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            Some(new_update_authority.pubkey()),
+            None,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_update_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+}
+
+#[tokio::test]
+async fn fail_new_update_authority_not_signer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let new_update_authority = Keypair::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let mut instruction = instruction::update_metadata_accounts_v2(
+        id(),
+        test_metadata.pubkey,
+        context.payer.pubkey(),
+        Some(new_update_authority.pubkey()),
+        None,
+        None,
+    );
+    instruction.accounts[2].is_signer = false;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_transport_error!(
+        result,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+}
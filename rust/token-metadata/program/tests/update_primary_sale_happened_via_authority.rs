@@ -0,0 +1,237 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_metadata(context: &mut ProgramTestContext, test_metadata: &Metadata) {
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+}
+
+async fn approve_sale_authority(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    sale_authority: &Keypair,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::approve_sale_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            sale_authority.pubkey(),
+            context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success_update_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    create_metadata(&mut context, &test_metadata).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_primary_sale_happened_via_authority(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert!(metadata.primary_sale_happened);
+}
+
+#[tokio::test]
+async fn success_sale_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let sale_authority = Keypair::new();
+
+    create_metadata(&mut context, &test_metadata).await;
+    approve_sale_authority(&mut context, &test_metadata, &sale_authority).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_primary_sale_happened_via_authority(
+            id(),
+            test_metadata.pubkey,
+            sale_authority.pubkey(),
+            Some(instruction::find_sale_authority_record_address(
+                &id(),
+                &test_metadata.mint.pubkey(),
+                &sale_authority.pubkey(),
+            )),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &sale_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert!(metadata.primary_sale_happened);
+}
+
+#[tokio::test]
+async fn fail_unapproved_sale_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let sale_authority = Keypair::new();
+
+    create_metadata(&mut context, &test_metadata).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_primary_sale_happened_via_authority(
+            id(),
+            test_metadata.pubkey,
+            sale_authority.pubkey(),
+            Some(instruction::find_sale_authority_record_address(
+                &id(),
+                &test_metadata.mint.pubkey(),
+                &sale_authority.pubkey(),
+            )),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &sale_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidSaleAuthority);
+}
+
+#[tokio::test]
+async fn fail_revoked_sale_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let sale_authority = Keypair::new();
+
+    create_metadata(&mut context, &test_metadata).await;
+    approve_sale_authority(&mut context, &test_metadata, &sale_authority).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::revoke_sale_authority(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            context.payer.pubkey(),
+            sale_authority.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_primary_sale_happened_via_authority(
+            id(),
+            test_metadata.pubkey,
+            sale_authority.pubkey(),
+            Some(instruction::find_sale_authority_record_address(
+                &id(),
+                &test_metadata.mint.pubkey(),
+                &sale_authority.pubkey(),
+            )),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &sale_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidSaleAuthority);
+}
+
+#[tokio::test]
+async fn fail_revoked_in_same_transaction() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let sale_authority = Keypair::new();
+
+    create_metadata(&mut context, &test_metadata).await;
+    approve_sale_authority(&mut context, &test_metadata, &sale_authority).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::revoke_sale_authority(
+                id(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                context.payer.pubkey(),
+                sale_authority.pubkey(),
+            ),
+            instruction::update_primary_sale_happened_via_authority(
+                id(),
+                test_metadata.pubkey,
+                sale_authority.pubkey(),
+                Some(instruction::find_sale_authority_record_address(
+                    &id(),
+                    &test_metadata.mint.pubkey(),
+                    &sale_authority.pubkey(),
+                )),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &sale_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    match result {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(x),
+        )) => assert_eq!(
+            MetadataError::from_u32(x),
+            Some(MetadataError::InvalidSaleAuthority)
+        ),
+        _ => panic!("Unexpected error {:?}", result),
+    }
+}
@@ -0,0 +1,325 @@
+mod utils;
+
+use borsh::BorshDeserialize;
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::{DataV2, UseAuthorityRecord, UseMethod, Uses};
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn create_with_uses(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    use_method: UseMethod,
+    total: u64,
+) {
+    test_metadata
+        .create_v2(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            None,
+            Some(Uses {
+                use_method,
+                remaining: total,
+                total,
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+async fn utilize(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    use_authority: Option<&Keypair>,
+    number_of_uses: u64,
+) -> transport::Result<()> {
+    let mut signers = vec![&context.payer];
+    let use_authority_pubkey = match use_authority {
+        Some(use_authority) => {
+            signers.push(use_authority);
+            use_authority.pubkey()
+        }
+        None => context.payer.pubkey(),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::utilize(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            use_authority_pubkey,
+            context.payer.pubkey(),
+            number_of_uses,
+        )],
+        Some(&context.payer.pubkey()),
+        &signers,
+        context.last_blockhash,
+    );
+
+    Ok(context.banks_client.process_transaction(tx).await?)
+}
+
+async fn approve_use_authority(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    use_authority: &Keypair,
+    number_of_uses: u64,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::approve_use_authority(
+            id(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            use_authority.pubkey(),
+            test_metadata.token.pubkey(),
+            test_metadata.pubkey,
+            test_metadata.mint.pubkey(),
+            number_of_uses,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success_owner_utilize() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Multiple, 5).await;
+    utilize(&mut context, &test_metadata, None, 2)
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(
+        metadata.uses,
+        Some(Uses {
+            use_method: UseMethod::Multiple,
+            remaining: 3,
+            total: 5,
+        })
+    );
+}
+
+#[tokio::test]
+async fn success_burn_on_last_use() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Burn, 1).await;
+    utilize(&mut context, &test_metadata, None, 1)
+        .await
+        .unwrap();
+
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 0);
+}
+
+#[tokio::test]
+async fn success_use_authority_burn_on_last_use() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let use_authority = Keypair::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Burn, 3).await;
+    approve_use_authority(&mut context, &test_metadata, &use_authority, 3).await;
+
+    utilize(&mut context, &test_metadata, Some(&use_authority), 1)
+        .await
+        .unwrap();
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 1);
+
+    utilize(&mut context, &test_metadata, Some(&use_authority), 2)
+        .await
+        .unwrap();
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 0);
+
+    let owner = context.payer.pubkey();
+    let record_account = get_account(
+        &mut context,
+        &instruction::find_use_authority_record_address(
+            &id(),
+            &test_metadata.mint.pubkey(),
+            &owner,
+            &use_authority.pubkey(),
+        ),
+    )
+    .await;
+    let record = UseAuthorityRecord::try_from_slice(&record_account.data).unwrap();
+    assert_eq!(record.allowed_uses, 0);
+}
+
+#[tokio::test]
+async fn fail_use_authority_over_allowance() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let use_authority = Keypair::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Multiple, 5).await;
+    approve_use_authority(&mut context, &test_metadata, &use_authority, 1).await;
+
+    let result = utilize(&mut context, &test_metadata, Some(&use_authority), 2)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::NotEnoughUses);
+}
+
+#[tokio::test]
+async fn fail_use_authority_after_transfer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+    let use_authority = Keypair::new();
+    let new_owner = Keypair::new();
+    let new_token = Keypair::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Multiple, 5).await;
+    approve_use_authority(&mut context, &test_metadata, &use_authority, 5).await;
+
+    create_token_account(
+        &mut context,
+        &new_token,
+        &test_metadata.mint.pubkey(),
+        &new_owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::transfer(
+            &spl_token::id(),
+            &test_metadata.token.pubkey(),
+            &new_token.pubkey(),
+            &context.payer.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The old owner's approval doesn't carry over to the new owner's record.
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::utilize(
+            id(),
+            test_metadata.pubkey,
+            new_token.pubkey(),
+            test_metadata.mint.pubkey(),
+            use_authority.pubkey(),
+            new_owner.pubkey(),
+            1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &use_authority],
+        context.last_blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::IncorrectOwner);
+}
+
+#[tokio::test]
+async fn fail_not_enough_uses() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Single, 1).await;
+    utilize(&mut context, &test_metadata, None, 1)
+        .await
+        .unwrap();
+
+    let result = utilize(&mut context, &test_metadata, None, 2)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::NotEnoughUses);
+}
+
+#[tokio::test]
+async fn fail_invalid_single_use() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    let result = test_metadata
+        .create_v2(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            None,
+            Some(Uses {
+                use_method: UseMethod::Single,
+                remaining: 2,
+                total: 2,
+            }),
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidUseMethod);
+}
+
+#[tokio::test]
+async fn fail_change_uses_after_first_use() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    create_with_uses(&mut context, &test_metadata, UseMethod::Multiple, 5).await;
+    utilize(&mut context, &test_metadata, None, 1)
+        .await
+        .unwrap();
+
+    let result = test_metadata
+        .update_v2(
+            &mut context,
+            DataV2 {
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                uri: "uri".to_string(),
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: None,
+                uses: Some(Uses {
+                    use_method: UseMethod::Multiple,
+                    remaining: 10,
+                    total: 10,
+                }),
+                content_hash: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CannotChangeUsesAfterFirstUse);
+}
@@ -7,7 +7,7 @@ use solana_sdk::{
 };
 use metaplex_token_metadata::{
     id, instruction,
-    state::{Creator, Data, PREFIX},
+    state::{Collection, Creator, Data, DataV2, Uses, PREFIX},
 };
 
 #[derive(Debug)]
@@ -93,6 +93,64 @@ impl Metadata {
         Ok(context.banks_client.process_transaction(tx).await?)
     }
 
+    pub async fn create_v2(
+        &self,
+        context: &mut ProgramTestContext,
+        name: String,
+        symbol: String,
+        uri: String,
+        creators: Option<Vec<Creator>>,
+        seller_fee_basis_points: u16,
+        is_mutable: bool,
+        collection: Option<Collection>,
+        uses: Option<Uses>,
+        content_hash: Option<[u8; 32]>,
+    ) -> transport::Result<()> {
+        create_mint(context, &self.mint, &context.payer.pubkey(), None).await?;
+        create_token_account(
+            context,
+            &self.token,
+            &self.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &self.mint.pubkey(),
+            &self.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::create_metadata_accounts_v2(
+                id(),
+                self.pubkey.clone(),
+                self.mint.pubkey(),
+                context.payer.pubkey().clone(),
+                context.payer.pubkey().clone(),
+                context.payer.pubkey().clone(),
+                name,
+                symbol,
+                uri,
+                creators,
+                seller_fee_basis_points,
+                false,
+                is_mutable,
+                collection,
+                uses,
+                content_hash,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
     pub async fn update_primary_sale_happened_via_token(
         &self,
         context: &mut ProgramTestContext,
@@ -143,4 +201,26 @@ impl Metadata {
 
         Ok(context.banks_client.process_transaction(tx).await?)
     }
+
+    pub async fn update_v2(
+        &self,
+        context: &mut ProgramTestContext,
+        data: DataV2,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::update_metadata_accounts_v2(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                None,
+                Some(data),
+                None,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
 }
@@ -0,0 +1,258 @@
+mod utils;
+
+use borsh::BorshSerialize;
+use num_traits::FromPrimitive;
+use solana_program::rent::Rent;
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::{DataV2, MAX_METADATA_LEN, MAX_VARIABLE_METADATA_LEN};
+use metaplex_token_metadata::utils::puff_out_data_fields;
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+fn long_uri() -> String {
+    "https://ipfs.io/ipfs/".to_string() + &"a".repeat(700) + "/0.json?ext=json"
+}
+
+#[tokio::test]
+async fn success_create_unpadded() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let account = get_account(&mut context, &test_metadata.pubkey).await;
+
+    assert_eq!(metadata.data.name, "Test");
+    assert_eq!(metadata.data.uri, "uri");
+    assert_eq!(account.data.len(), MAX_VARIABLE_METADATA_LEN);
+}
+
+#[tokio::test]
+async fn success_lengthen_and_shorten() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    test_metadata
+        .update_v2(
+            &mut context,
+            DataV2 {
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                uri: long_uri(),
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: None,
+                uses: None,
+                content_hash: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let account = get_account(&mut context, &test_metadata.pubkey).await;
+
+    assert_eq!(metadata.data.uri, long_uri());
+    assert_eq!(account.data.len(), MAX_VARIABLE_METADATA_LEN);
+
+    test_metadata
+        .update_v2(
+            &mut context,
+            DataV2 {
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                uri: "uri".to_string(),
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: None,
+                uses: None,
+                content_hash: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let account = get_account(&mut context, &test_metadata.pubkey).await;
+
+    assert_eq!(metadata.data.uri, "uri");
+    assert_eq!(account.data.len(), MAX_VARIABLE_METADATA_LEN);
+    // Nothing is left of the longer uri past the end of the metadata.
+    let len = metadata.try_to_vec().unwrap().len();
+    assert!(account.data[len..].iter().all(|byte| *byte == 0));
+}
+
+#[tokio::test]
+async fn success_update_fixed_size_account() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Rewrite the account the way older versions of the program laid it out.
+    let mut metadata = test_metadata.get_data(&mut context).await;
+    metadata.collection = None;
+    puff_out_data_fields(&mut metadata);
+    let mut data = metadata.try_to_vec().unwrap();
+    data.resize(MAX_METADATA_LEN, 0);
+    context.set_account(
+        &test_metadata.pubkey,
+        &AccountSharedData::from(Account {
+            lamports: Rent::default().minimum_balance(MAX_METADATA_LEN),
+            data,
+            owner: id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.data.name.len(), 32);
+    assert_eq!(metadata.collection, None);
+
+    test_metadata
+        .update(
+            &mut context,
+            "Cool".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let account = get_account(&mut context, &test_metadata.pubkey).await;
+
+    assert_eq!(metadata.data.name, "Cool");
+    assert_eq!(account.data.len(), MAX_METADATA_LEN);
+}
+
+#[tokio::test]
+async fn fail_long_uri_in_fixed_size_account() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let mut data = metadata.try_to_vec().unwrap();
+    data.resize(MAX_METADATA_LEN, 0);
+    context.set_account(
+        &test_metadata.pubkey,
+        &AccountSharedData::from(Account {
+            lamports: Rent::default().minimum_balance(MAX_METADATA_LEN),
+            data,
+            owner: id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let result = test_metadata
+        .update(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            long_uri(),
+            None,
+            10,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::MetadataTooLargeForAccount);
+}
+
+#[tokio::test]
+async fn fail_puff_variable_length_account() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::puff_metadata_account(
+            id(),
+            test_metadata.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CannotPuffVariableLengthMetadata);
+}
@@ -0,0 +1,296 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::{Collection, DataV2};
+use metaplex_token_metadata::{id, instruction};
+use utils::*;
+
+async fn setup_collection(
+    context: &mut ProgramTestContext,
+    max_supply: Option<u64>,
+) -> (Metadata, MasterEditionV2) {
+    let collection_metadata = Metadata::new();
+    let collection_master_edition = MasterEditionV2::new(&collection_metadata);
+
+    collection_metadata
+        .create(
+            context,
+            "Collection".to_string(),
+            "COL".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    collection_master_edition
+        .create(context, max_supply)
+        .await
+        .unwrap();
+
+    (collection_metadata, collection_master_edition)
+}
+
+async fn create_member(context: &mut ProgramTestContext, collection: &Metadata) -> Metadata {
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create_v2(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            Some(Collection {
+                verified: false,
+                key: collection.mint.pubkey(),
+            }),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    test_metadata
+}
+
+async fn verify_member(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    collection_metadata: &Metadata,
+    collection_master_edition: &MasterEditionV2,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::verify_collection(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            collection_metadata.mint.pubkey(),
+            collection_metadata.pubkey,
+            collection_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let (collection_metadata, collection_master_edition) =
+        setup_collection(&mut context, Some(0)).await;
+    let test_metadata = create_member(&mut context, &collection_metadata).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::verify_collection(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            collection_metadata.mint.pubkey(),
+            collection_metadata.pubkey,
+            collection_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let collection = metadata.collection.unwrap();
+    assert_eq!(collection.key, collection_metadata.mint.pubkey());
+    assert_eq!(collection.verified, true);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::unverify_collection(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            collection_metadata.mint.pubkey(),
+            collection_metadata.pubkey,
+            collection_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.collection.unwrap().verified, false);
+}
+
+#[tokio::test]
+async fn fail_invalid_collection_authority() {
+    let mut context = program_test().start_with_context().await;
+    let (collection_metadata, collection_master_edition) =
+        setup_collection(&mut context, Some(0)).await;
+    let test_metadata = create_member(&mut context, &collection_metadata).await;
+    let fake_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::verify_collection(
+            id(),
+            test_metadata.pubkey,
+            fake_authority.pubkey(),
+            collection_metadata.mint.pubkey(),
+            collection_metadata.pubkey,
+            collection_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UpdateAuthorityIncorrect);
+}
+
+#[tokio::test]
+async fn fail_collection_not_unique_master_edition() {
+    let mut context = program_test().start_with_context().await;
+    let (collection_metadata, collection_master_edition) =
+        setup_collection(&mut context, Some(10)).await;
+    let test_metadata = create_member(&mut context, &collection_metadata).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::verify_collection(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            collection_metadata.mint.pubkey(),
+            collection_metadata.pubkey,
+            collection_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CollectionMustBeAUniqueMasterEdition);
+}
+
+#[tokio::test]
+async fn fail_create_with_verified_collection() {
+    let mut context = program_test().start_with_context().await;
+    let (collection_metadata, _) = setup_collection(&mut context, Some(0)).await;
+    let test_metadata = Metadata::new();
+
+    let result = test_metadata
+        .create_v2(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            Some(Collection {
+                verified: true,
+                key: collection_metadata.mint.pubkey(),
+            }),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(
+        result,
+        MetadataError::CollectionCannotBeVerifiedInThisInstruction
+    );
+}
+
+#[tokio::test]
+async fn fail_update_verified_collection() {
+    let mut context = program_test().start_with_context().await;
+    let (collection_metadata, collection_master_edition) =
+        setup_collection(&mut context, Some(0)).await;
+    let (other_collection_metadata, _) = setup_collection(&mut context, Some(0)).await;
+    let test_metadata = create_member(&mut context, &collection_metadata).await;
+    verify_member(
+        &mut context,
+        &test_metadata,
+        &collection_metadata,
+        &collection_master_edition,
+    )
+    .await;
+
+    let result = test_metadata
+        .update_v2(
+            &mut context,
+            DataV2 {
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                uri: "uri".to_string(),
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: Some(Collection {
+                    verified: false,
+                    key: other_collection_metadata.mint.pubkey(),
+                }),
+                uses: None,
+                content_hash: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::VerifiedCollectionCannotBeChanged);
+}
+
+#[tokio::test]
+async fn prints_start_unverified() {
+    let mut context = program_test().start_with_context().await;
+    let (collection_metadata, collection_master_edition) =
+        setup_collection(&mut context, Some(0)).await;
+    let test_metadata = create_member(&mut context, &collection_metadata).await;
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    test_master_edition
+        .create(&mut context, Some(10))
+        .await
+        .unwrap();
+    verify_member(
+        &mut context,
+        &test_metadata,
+        &collection_metadata,
+        &collection_master_edition,
+    )
+    .await;
+
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let account = get_account(&mut context, &test_edition_marker.new_metadata_pubkey).await;
+    let print: metaplex_token_metadata::state::Metadata =
+        try_from_slice_unchecked(&account.data).unwrap();
+    let collection = print.collection.unwrap();
+    assert_eq!(collection.key, collection_metadata.mint.pubkey());
+    assert_eq!(collection.verified, false);
+}
@@ -5,12 +5,12 @@ use {
     metaplex_token_metadata::{
         instruction::{
             create_master_edition, create_metadata_accounts,
-            mint_new_edition_from_master_edition_via_token, puff_metadata_account,
-            update_metadata_accounts,
+            mint_new_edition_from_master_edition_via_token, propose_update_authority,
+            puff_metadata_account, update_metadata_accounts,
         },
         state::{
             get_reservation_list, Data, Edition, Key, MasterEditionV1, MasterEditionV2, Metadata,
-            EDITION, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
+            EDITION, MAX_METADATA_LEN, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
         },
     },
     solana_clap_utils::{
@@ -19,7 +19,10 @@ use {
     },
     solana_client::rpc_client::RpcClient,
     solana_program::{
-        account_info::AccountInfo, borsh::try_from_slice_unchecked, program_pack::Pack,
+        account_info::AccountInfo,
+        borsh::try_from_slice_unchecked,
+        hash::{hash, Hash},
+        program_pack::Pack,
     },
     solana_sdk::{
         pubkey::Pubkey,
@@ -41,7 +44,8 @@ fn puff_unpuffed_metadata(_app_matches: &ArgMatches, payer: Keypair, client: Rpc
         .unwrap();
     let mut needing_puffing = vec![];
     for acct in metadata_accounts {
-        if acct.1.data[0] == Key::MetadataV1 as u8 {
+        // Only the older, fixed size accounts can be puffed.
+        if acct.1.data[0] == Key::MetadataV1 as u8 && acct.1.data.len() == MAX_METADATA_LEN {
             match try_from_slice_unchecked(&acct.1.data) {
                 Ok(val) => {
                     let account: Metadata = val;
@@ -222,6 +226,45 @@ fn show(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
     }
 }
 
+fn verify_content_hash(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
+    let program_key = metaplex_token_metadata::id();
+
+    let mint_key = pubkey_of(app_matches, "mint").unwrap();
+    let metadata_seeds = &[PREFIX.as_bytes(), &program_key.as_ref(), mint_key.as_ref()];
+    let (metadata_key, _) = Pubkey::find_program_address(metadata_seeds, &program_key);
+
+    let metadata_account = client.get_account(&metadata_key).unwrap();
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    let content = std::fs::read(app_matches.value_of("file").unwrap()).unwrap();
+    let content_hash = hash(&content);
+
+    match metadata.content_hash {
+        Some(expected) if Hash::new_from_array(expected) == content_hash => {
+            println!(
+                "Content hash {} matches metadata {}",
+                content_hash, metadata_key
+            );
+            if metadata.is_mutable {
+                println!("Warning: metadata is still mutable, so its content hash can change");
+            }
+        }
+        Some(expected) => {
+            println!(
+                "Content hash mismatch: metadata {} records {} but the file hashes to {}",
+                metadata_key,
+                Hash::new_from_array(expected),
+                content_hash
+            );
+            std::process::exit(1);
+        }
+        None => {
+            println!("Metadata {} has no content hash", metadata_key);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn mint_edition_via_token_call(
     app_matches: &ArgMatches,
     payer: Keypair,
@@ -506,18 +549,30 @@ fn update_metadata_account_call(
         creators: metadata.data.creators,
     };
 
-    let instructions = [update_metadata_accounts(
+    let mut instructions = vec![update_metadata_accounts(
         program_key,
         metadata_key,
         update_authority.pubkey(),
-        new_update_authority,
+        None,
         Some(new_data),
         None,
     )];
 
+    // The new update authority only takes over once it accepts the proposal.
+    if let Some(new_update_authority) = new_update_authority {
+        instructions.push(propose_update_authority(
+            program_key,
+            metadata_key,
+            metadata.mint,
+            update_authority.pubkey(),
+            new_update_authority,
+            payer.pubkey(),
+        ));
+    }
+
     let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
     let recent_blockhash = client.get_recent_blockhash().unwrap().0;
-    let signers = vec![&update_authority];
+    let signers = vec![&payer, &update_authority];
 
     transaction.sign(&signers, recent_blockhash);
     client.send_and_confirm_transaction(&transaction).unwrap();
@@ -749,7 +804,7 @@ fn main() {
                         .required(false)
                         .validator(is_valid_pubkey)
                         .takes_value(true)
-                        .help("New update authority"))
+                        .help("Propose a new update authority, which takes over once it accepts"))
         ).subcommand(
             SubCommand::with_name("show")
                 .about("Show")
@@ -762,6 +817,25 @@ fn main() {
                         .takes_value(true)
                         .help("Metadata mint"),
                 )
+        ).subcommand(
+            SubCommand::with_name("verify_content_hash")
+                .about("Check a local copy of the metadata's JSON against the content hash recorded on chain")
+                .arg(
+                    Arg::with_name("mint")
+                        .long("mint")
+                        .value_name("MINT")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Metadata mint"),
+                ).arg(
+                    Arg::with_name("file")
+                        .long("file")
+                        .value_name("FILE")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Path to the JSON file to hash"),
+                )
         ).subcommand(
             SubCommand::with_name("show_reservation_list")
                 .about("Show Reservation List")
@@ -886,6 +960,9 @@ fn main() {
         ("show", Some(arg_matches)) => {
             show(arg_matches, payer, client);
         }
+        ("verify_content_hash", Some(arg_matches)) => {
+            verify_content_hash(arg_matches, payer, client);
+        }
         ("show_reservation_list", Some(arg_matches)) => {
             show_reservation_list(arg_matches, payer, client);
         }